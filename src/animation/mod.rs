@@ -3,7 +3,15 @@ use std::{cell::RefCell, rc::Rc};
 use nalgebra::{Point3, Vector3};
 
 use crate::{
-    mobjects::{text::Text, Mobject, MobjectClone, SimpleLine},
+    mobjects::{
+        arena::MobjectId,
+        chart::DataChart,
+        effects::{Effect, Effected},
+        graph::RiemannRectangles,
+        group::MobjectGroup,
+        text::Text,
+        Draw, Mobject, MobjectClone, SimpleLine,
+    },
     video_backend::{FFMPEGEncoder, VideoBackendController},
     Context, GMFloat, Scene,
 };
@@ -21,7 +29,7 @@ pub struct SimpleMovement {
     pub displacement: Vector3<GMFloat>,
     pub scene: Rc<RefCell<Scene>>,
     pub ctx: Rc<RefCell<Context>>,
-    pub m: Rc<RefCell<Box<dyn Mobject>>>,
+    pub m: MobjectId,
     pub animation_config: AnimationConfig,
     pub last_progress: GMFloat,
 }
@@ -30,8 +38,8 @@ pub struct MovementPrecise {
     pub displacement: Vector3<GMFloat>,
     pub scene: Rc<RefCell<Scene>>,
     pub ctx: Rc<RefCell<Context>>,
-    pub m: Rc<RefCell<Box<dyn Mobject>>>,
-    pub start_mobject: Rc<RefCell<Box<dyn MobjectClone>>>,
+    pub m: MobjectId,
+    pub start_mobject: Box<dyn Mobject>,
     pub animation_config: AnimationConfig,
 }
 
@@ -55,14 +63,11 @@ impl Iterator for SimpleMovement {
         self.last_progress = progress;
 
         let translation = nalgebra::Matrix4::new_translation(&(self.displacement * delta_progress));
-        self.m
-            .borrow_mut()
-            .transform(nalgebra::Transform::from_matrix_unchecked(translation));
-        // Some()
-        for d in &self.scene.borrow().mobjects {
-            self.ctx.borrow_mut().clear_transparent();
-            d.borrow().draw(&mut *self.ctx.borrow_mut());
+        if let Some(m) = self.scene.borrow_mut().get_mut(self.m) {
+            m.transform(nalgebra::Transform::from_matrix_unchecked(translation));
         }
+        // Some()
+        self.scene.borrow().render(&mut self.ctx.borrow_mut());
         Some(self.ctx.borrow().image_bytes().to_vec())
     }
 }
@@ -82,7 +87,7 @@ pub struct SimpleRotate {
     pub point: Point3<GMFloat>,
     pub scene: Rc<RefCell<Scene>>,
     pub ctx: Rc<RefCell<Context>>,
-    pub m: Rc<RefCell<Box<dyn Mobject>>>,
+    pub m: MobjectId,
     pub animation_config: AnimationConfig,
     pub last_progress: GMFloat,
 }
@@ -106,13 +111,10 @@ impl Iterator for SimpleRotate {
         self.last_progress = progress;
         let rotation_matrix =
             nalgebra::Matrix4::new_rotation_wrt_point(self.axisangle * delta_progress, self.point);
-        self.m
-            .borrow_mut()
-            .transform(nalgebra::Transform::from_matrix_unchecked(rotation_matrix));
-        for d in &self.scene.borrow().mobjects {
-            self.ctx.borrow_mut().clear_transparent();
-            d.borrow().draw(&mut *self.ctx.borrow_mut());
+        if let Some(m) = self.scene.borrow_mut().get_mut(self.m) {
+            m.transform(nalgebra::Transform::from_matrix_unchecked(rotation_matrix));
         }
+        self.scene.borrow().render(&mut self.ctx.borrow_mut());
         Some(self.ctx.borrow().image_bytes().to_vec())
     }
 }
@@ -121,20 +123,18 @@ impl Iterator for SimpleRotate {
 fn test_simple_move() {
     let mut ctx = Context::default();
     let mut scene = Scene::default();
-    let mut line: Box<dyn Mobject> = Box::new(SimpleLine {
+    let line = scene.add(Box::new(SimpleLine {
         p0: Point3::new(0.0, 0.0, 0.0),
         p1: Point3::new(1.0, 1.0, 0.0),
         draw_config: Default::default(),
-    });
-    let line_ref = Rc::new(RefCell::new(line));
-    scene.add_ref(line_ref.clone());
+    }));
     let scene = Rc::new(RefCell::new(scene));
     let ctx = Rc::new(RefCell::new(ctx));
     let simple_move = SimpleMovement {
         displacement: Vector3::new(2.0, 0.0, 0.0),
         scene: scene.clone(),
         ctx: ctx.clone(),
-        m: line_ref.clone(),
+        m: line,
         animation_config: AnimationConfig {
             total_frame: 60 * 30,
             current_frame: 0,
@@ -152,6 +152,7 @@ fn test_simple_move() {
         output_height: 1080,
         output_width: 1920,
         color_order: ColorOrder::Rgba,
+        alpha: false,
     };
     let mut video_backend_var = VideoBackend {
         backend_type: VideoBackendType::FFMPEG(FFMPEGBackend::new(
@@ -191,10 +192,7 @@ impl Iterator for Wait {
             return None;
         }
         if self.is_first_frame {
-            for d in &self.scene.borrow().mobjects {
-                self.ctx.borrow_mut().clear_transparent();
-                d.borrow().draw(&mut *self.ctx.borrow_mut());
-            }
+            self.scene.borrow().render(&mut self.ctx.borrow_mut());
             self.is_first_frame = false;
         }
         Some(self.ctx.borrow().image_bytes().to_vec())
@@ -207,17 +205,269 @@ impl Animation for Wait {
     }
 }
 
+/// shows a mobject at this point of the timeline, then holds the frame like `Wait`;
+/// add the mobject up front and hide it with `Scene::set_visible` to get its id early
+pub struct Add {
+    pub scene: Rc<RefCell<Scene>>,
+    pub ctx: Rc<RefCell<Context>>,
+    pub m: MobjectId,
+    pub animation_config: AnimationConfig,
+}
+
+impl Iterator for Add {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.animation_config.current_frame += 1;
+        let current_frame = self.animation_config.current_frame;
+        let total_frame = self.animation_config.total_frame;
+        if current_frame > total_frame {
+            return None;
+        }
+        if current_frame == 1 {
+            self.scene.borrow_mut().set_visible(self.m, true);
+            self.scene.borrow().render(&mut self.ctx.borrow_mut());
+        }
+        Some(self.ctx.borrow().image_bytes().to_vec())
+    }
+}
+
+impl Animation for Add {
+    fn total_frame(&self) -> u32 {
+        self.animation_config.total_frame
+    }
+}
+
+/// takes a mobject out of the scene at this point of the timeline, then holds the frame
+pub struct Remove {
+    pub scene: Rc<RefCell<Scene>>,
+    pub ctx: Rc<RefCell<Context>>,
+    pub m: MobjectId,
+    pub animation_config: AnimationConfig,
+}
+
+impl Iterator for Remove {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.animation_config.current_frame += 1;
+        let current_frame = self.animation_config.current_frame;
+        let total_frame = self.animation_config.total_frame;
+        if current_frame > total_frame {
+            return None;
+        }
+        if current_frame == 1 {
+            self.scene.borrow_mut().remove(self.m);
+            self.scene.borrow().render(&mut self.ctx.borrow_mut());
+        }
+        Some(self.ctx.borrow().image_bytes().to_vec())
+    }
+}
+
+impl Animation for Remove {
+    fn total_frame(&self) -> u32 {
+        self.animation_config.total_frame
+    }
+}
+
+/// moves a mobject back to the state saved with `Scene::save_state`; the control points
+/// are interpolated when both states are of the same type with the same number of them,
+/// otherwise the mobject crossfades into the saved state, e.g. for charts
+pub struct Restore {
+    pub scene: Rc<RefCell<Scene>>,
+    pub ctx: Rc<RefCell<Context>>,
+    pub m: MobjectId,
+    pub animation_config: AnimationConfig,
+    /// the mobject as it was on the first frame
+    pub start: Option<Box<dyn Mobject>>,
+}
+
+impl Iterator for Restore {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.animation_config.current_frame += 1;
+        let current_frame = self.animation_config.current_frame;
+        let total_frame = self.animation_config.total_frame;
+        if current_frame > total_frame {
+            return None;
+        }
+        let progress = (self.animation_config.rate_function)(
+            current_frame as GMFloat / total_frame as GMFloat,
+        );
+        let mut scene = self.scene.borrow_mut();
+        if self.start.is_none() {
+            self.start = scene.get(self.m).map(|m| m.mobject_clone());
+        }
+        let mut crossfade = None;
+        if current_frame == total_frame {
+            scene.restore(self.m);
+        } else if let (Some(start), Some(saved)) = (
+            &self.start,
+            scene.saved_state(self.m).map(|s| s.mobject_clone()),
+        ) {
+            let (from, to) = (start.control_points(), saved.control_points());
+            let same_type = start.as_any().type_id() == saved.as_any().type_id();
+            if same_type && !from.is_empty() && from.len() == to.len() {
+                let points: Vec<_> = from
+                    .iter()
+                    .zip(&to)
+                    .map(|(a, b)| a + (b - a) * progress)
+                    .collect();
+                if let Some(m) = scene.get_mut(self.m) {
+                    m.set_control_points(&points);
+                }
+            } else if scene.is_visible(self.m) {
+                let fade = |m: Box<dyn Mobject>, opacity| -> Box<dyn Mobject> {
+                    Box::new(MobjectGroup {
+                        opacity,
+                        ..MobjectGroup::new(vec![m])
+                    })
+                };
+                crossfade = scene.get(self.m).map(|current| {
+                    MobjectGroup::new(vec![
+                        fade(current.mobject_clone(), 1.0 - progress),
+                        fade(saved, progress),
+                    ])
+                });
+            }
+        }
+        let mut ctx = self.ctx.borrow_mut();
+        match crossfade {
+            // the mobject stays in the scene as it is, typed handles keep working, and
+            // both states are drawn over the frame in its place
+            Some(crossfade) => {
+                scene.set_visible(self.m, false);
+                scene.render(&mut ctx);
+                scene.set_visible(self.m, true);
+                crossfade.draw(&mut ctx);
+            }
+            None => scene.render(&mut ctx),
+        }
+        Some(ctx.image_bytes().to_vec())
+    }
+}
+
+impl Animation for Restore {
+    fn total_frame(&self) -> u32 {
+        self.animation_config.total_frame
+    }
+}
+
+pub struct RiemannTransition {
+    pub target_partition: usize,
+    pub scene: Rc<RefCell<Scene>>,
+    pub ctx: Rc<RefCell<Context>>,
+    pub m: MobjectId<RiemannRectangles>,
+    pub animation_config: AnimationConfig,
+    pub start_partition: Option<usize>,
+}
+
+impl Iterator for RiemannTransition {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.animation_config.current_frame += 1;
+        let current_frame = self.animation_config.current_frame;
+        let total_frame = self.animation_config.total_frame;
+        if current_frame > total_frame {
+            return None;
+        }
+        let progress = (self.animation_config.rate_function)(
+            current_frame as GMFloat / total_frame as GMFloat,
+        );
+        if let Some(m) = self.scene.borrow_mut().get_mut(self.m) {
+            let start_partition = *self.start_partition.get_or_insert(m.partition());
+            m.interpolate_partition(start_partition, self.target_partition, progress);
+        }
+        self.scene.borrow().render(&mut self.ctx.borrow_mut());
+        Some(self.ctx.borrow().image_bytes().to_vec())
+    }
+}
+
+impl Animation for RiemannTransition {
+    fn total_frame(&self) -> u32 {
+        self.animation_config.total_frame
+    }
+}
+
+pub struct ChartTransition<T: DataChart + 'static> {
+    pub target_values: Vec<GMFloat>,
+    pub scene: Rc<RefCell<Scene>>,
+    pub ctx: Rc<RefCell<Context>>,
+    pub m: MobjectId<T>,
+    pub animation_config: AnimationConfig,
+    pub start_values: Option<Vec<GMFloat>>,
+}
+
+impl<T: DataChart + 'static> Iterator for ChartTransition<T> {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.animation_config.current_frame += 1;
+        let current_frame = self.animation_config.current_frame;
+        let total_frame = self.animation_config.total_frame;
+        if current_frame > total_frame {
+            return None;
+        }
+        let progress = (self.animation_config.rate_function)(
+            current_frame as GMFloat / total_frame as GMFloat,
+        );
+        if let Some(m) = self.scene.borrow_mut().get_mut(self.m) {
+            let start_values = self.start_values.get_or_insert_with(|| m.values().to_vec());
+            m.interpolate_values(start_values, &self.target_values, progress);
+        }
+        self.scene.borrow().render(&mut self.ctx.borrow_mut());
+        Some(self.ctx.borrow().image_bytes().to_vec())
+    }
+}
+
+impl<T: DataChart + 'static> Animation for ChartTransition<T> {
+    fn total_frame(&self) -> u32 {
+        self.animation_config.total_frame
+    }
+}
+
+pub struct EffectTransition {
+    pub target_effects: Vec<Effect>,
+    pub scene: Rc<RefCell<Scene>>,
+    pub ctx: Rc<RefCell<Context>>,
+    pub m: MobjectId<Effected>,
+    pub animation_config: AnimationConfig,
+    pub start_effects: Option<Vec<Effect>>,
+}
+
+impl Iterator for EffectTransition {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.animation_config.current_frame += 1;
+        let current_frame = self.animation_config.current_frame;
+        let total_frame = self.animation_config.total_frame;
+        if current_frame > total_frame {
+            return None;
+        }
+        let progress = (self.animation_config.rate_function)(
+            current_frame as GMFloat / total_frame as GMFloat,
+        );
+        if let Some(m) = self.scene.borrow_mut().get_mut(self.m) {
+            let start_effects = self.start_effects.get_or_insert_with(|| m.effects.clone());
+            m.interpolate_effects(start_effects, &self.target_effects, progress);
+        }
+        self.scene.borrow().render(&mut self.ctx.borrow_mut());
+        Some(self.ctx.borrow().image_bytes().to_vec())
+    }
+}
+
+impl Animation for EffectTransition {
+    fn total_frame(&self) -> u32 {
+        self.animation_config.total_frame
+    }
+}
+
 #[test]
 fn test_simple_rotate() {
     let mut ctx = Context::default();
     let mut scene = Scene::default();
-    let mut line: Box<dyn Mobject> = Box::new(SimpleLine {
+    let line = scene.add(Box::new(SimpleLine {
         p0: Point3::new(0.0, 0.0, 0.0),
         p1: Point3::new(1.0, 1.0, 0.0),
         draw_config: Default::default(),
-    });
-    let line_ref = Rc::new(RefCell::new(line));
-    scene.add_ref(line_ref.clone());
+    }));
     let scene = Rc::new(RefCell::new(scene));
     let ctx = Rc::new(RefCell::new(ctx));
     let simple_move = SimpleRotate {
@@ -225,7 +475,7 @@ fn test_simple_rotate() {
         point: Point3::origin(),
         scene: scene.clone(),
         ctx: ctx.clone(),
-        m: line_ref.clone(),
+        m: line,
         animation_config: AnimationConfig {
             total_frame: 240,
             current_frame: 0,
@@ -253,6 +503,7 @@ fn test_simple_rotate() {
         output_height: 1080,
         output_width: 1920,
         color_order: ColorOrder::Rgba,
+        alpha: false,
     };
     let mut video_backend_var = VideoBackend {
         backend_type: VideoBackendType::FFMPEG(FFMPEGBackend::new(
@@ -268,3 +519,160 @@ fn test_simple_rotate() {
         video_backend_var.write_frame(&frame);
     }
 }
+
+#[test]
+fn test_add_remove() {
+    use crate::mobjects::Rectangle;
+    let mut scene = Scene::default();
+    let square = scene.add(Box::new(Rectangle::default()));
+    let line = scene.add(Box::new(SimpleLine::default()));
+    scene.set_visible(square, false);
+    let line = scene
+        .replace(line, Box::new(SimpleLine::default()))
+        .unwrap();
+    assert_eq!(scene.ids(), &[square, line]);
+
+    let scene = Rc::new(RefCell::new(scene));
+    let ctx = Rc::new(RefCell::new(Context::default()));
+    let config = || AnimationConfig {
+        total_frame: 2,
+        current_frame: 0,
+        rate_function: |x| x,
+    };
+    let add = Add {
+        scene: scene.clone(),
+        ctx: ctx.clone(),
+        m: square,
+        animation_config: config(),
+    };
+    let frames: Vec<_> = add.collect();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0], frames[1]);
+    assert!(scene.borrow().is_visible(square));
+    let remove = Remove {
+        scene: scene.clone(),
+        ctx: ctx.clone(),
+        m: line,
+        animation_config: config(),
+    };
+    assert_ne!(remove.last().unwrap(), frames[0]);
+    assert_eq!(scene.borrow().ids(), &[square]);
+    scene.borrow_mut().clear();
+    assert!(scene.borrow().is_empty());
+}
+
+#[test]
+fn test_restore() {
+    use crate::mobjects::{
+        chart::BarChart,
+        table::{Table, DEFAULT_CELL_BUFFER},
+        DrawConfig, Rectangle, Transform,
+    };
+    let mut group = MobjectGroup::new(vec![
+        Box::new(Rectangle::default()),
+        Box::new(Text::new(
+            "saved".to_owned(),
+            Point3::new(-1.0, 2.0, 0.0),
+            8.0,
+            DrawConfig::filled(crate::Color::default()),
+        )),
+    ]);
+    // deep copy, the original doesn't follow the copy around
+    let mut copy = group.clone();
+    copy.move_this(Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!(copy.get_left().x - group.get_left().x, 1.0);
+    assert!(group.become_mobject(&copy));
+    assert_eq!(group.get_left(), copy.get_left());
+    let mut square = Rectangle::default();
+    assert!(!square.become_mobject(&copy));
+
+    let mut scene = Scene::default();
+    let m = scene.add(Box::new(group));
+    scene.save_state(m);
+    let saved = scene.get(m).unwrap().bounding_box();
+    scene.get_mut(m).unwrap().scale(0.5);
+    scene
+        .get_mut(m)
+        .unwrap()
+        .move_this(Vector3::new(-4.0, -2.0, 0.0));
+
+    let scene = Rc::new(RefCell::new(scene));
+    let ctx = Rc::new(RefCell::new(Context::default()));
+    let mut restore = Restore {
+        scene: scene.clone(),
+        ctx: ctx.clone(),
+        m,
+        animation_config: AnimationConfig {
+            total_frame: 4,
+            current_frame: 0,
+            rate_function: |x| x,
+        },
+        start: None,
+    };
+    let start = scene.borrow().get(m).unwrap().get_center();
+    restore.next();
+    let quarter = scene.borrow().get(m).unwrap().get_center();
+    let target = saved.unwrap().center();
+    assert!((quarter - (start + (target - start) * 0.25)).norm() < 1e-4);
+    assert_eq!(restore.count(), 3);
+    assert_eq!(scene.borrow().get(m).unwrap().bounding_box(), saved);
+
+    // swapping in another type keeps the id
+    assert!(scene.borrow_mut().become_mobject(m, &square));
+    assert_eq!(scene.borrow().ids(), &[m]);
+    assert!(scene.borrow().get(m).unwrap().submobjects().is_empty());
+    scene
+        .borrow()
+        .save_png(&mut ctx.borrow_mut(), "restore.png");
+
+    let restore = |m: MobjectId| Restore {
+        scene: scene.clone(),
+        ctx: ctx.clone(),
+        m,
+        animation_config: AnimationConfig {
+            total_frame: 4,
+            current_frame: 0,
+            rate_function: |x| x,
+        },
+        start: None,
+    };
+    // grid lines and cell boxes of a table move along with the cells
+    let table = scene.borrow_mut().add_typed(Table::new(
+        vec![vec!["1".to_owned(), "2".to_owned()]],
+        DEFAULT_CELL_BUFFER,
+        true,
+    ));
+    scene.borrow_mut().save_state(table);
+    let width = scene.borrow().get(table).unwrap().width();
+    scene.borrow_mut().get_mut(table).unwrap().scale(2.0);
+    let mut animation = restore(table.untyped());
+    animation.next();
+    let quarter = scene.borrow().get(table).unwrap().width();
+    assert!((quarter - 1.75 * width).abs() < 1e-4);
+    assert_eq!(animation.count(), 3);
+    assert!((scene.borrow().get(table).unwrap().width() - width).abs() < 1e-4);
+
+    // a chart has no control points and crossfades into its saved state
+    let chart = scene
+        .borrow_mut()
+        .add_typed(BarChart::new(vec![1.0, 2.0], vec![]));
+    scene.borrow_mut().save_state(chart);
+    let saved = scene.borrow().get(chart).unwrap().bounding_box();
+    scene
+        .borrow_mut()
+        .get_mut(chart)
+        .unwrap()
+        .move_this(Vector3::new(3.0, 0.0, 0.0));
+    let moved = scene.borrow().get(chart).unwrap().bounding_box();
+    let mut animation = restore(chart.untyped());
+    let first = animation.next().unwrap();
+    // the chart itself is left alone while both states are drawn
+    assert_eq!(scene.borrow().get(chart).unwrap().bounding_box(), moved);
+    assert!(scene.borrow().is_visible(chart));
+    assert_ne!(saved, moved);
+    scene.borrow().render(&mut ctx.borrow_mut());
+    assert_ne!(first, ctx.borrow().image_bytes().to_vec());
+    let last = animation.last().unwrap();
+    assert_ne!(first, last);
+    assert_eq!(scene.borrow().get(chart).unwrap().bounding_box(), saved);
+}
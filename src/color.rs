@@ -0,0 +1,561 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::GMFloat;
+
+/// 8 bit rgba color, not premultiplied
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// opaque color
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 0xff }
+    }
+
+    /// `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, the `#` is optional
+    pub fn from_hex(hex: &str) -> Result<Self, ColorParseError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let invalid = || ColorParseError::InvalidHex(hex.to_string());
+        if !digits.is_ascii() {
+            return Err(invalid());
+        }
+        let nibble = |i: usize| u8::from_str_radix(&digits[i..i + 1], 16).map(|v| v * 0x11);
+        let byte = |i: usize| u8::from_str_radix(&digits[2 * i..2 * i + 2], 16);
+        let channels: Result<Vec<u8>, _> = match digits.len() {
+            3 | 4 => (0..digits.len()).map(nibble).collect(),
+            6 | 8 => (0..digits.len() / 2).map(byte).collect(),
+            _ => return Err(invalid()),
+        };
+        let c = channels.map_err(|_| invalid())?;
+        Ok(Self::new(
+            c[0],
+            c[1],
+            c[2],
+            c.get(3).copied().unwrap_or(0xff),
+        ))
+    }
+
+    /// one of the css named colors, case insensitive
+    pub fn from_css_name(name: &str) -> Result<Self, ColorParseError> {
+        let lower = name.to_ascii_lowercase();
+        CSS_COLORS
+            .iter()
+            .find(|(n, _)| *n == lower)
+            .map(|(_, hex)| Self::from_hex(hex).unwrap())
+            .ok_or_else(|| ColorParseError::UnknownName(name.to_string()))
+    }
+
+    /// `#rrggbbaa`
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+
+    pub fn with_alpha(self, a: u8) -> Self {
+        Self { a, ..self }
+    }
+
+    /// channels as 0..1 floats
+    pub fn to_rgba(&self) -> [GMFloat; 4] {
+        [self.r, self.g, self.b, self.a].map(|c| c as GMFloat / 255.0)
+    }
+
+    /// from 0..1 floats, clamped
+    pub fn from_rgba(rgba: [GMFloat; 4]) -> Self {
+        let [r, g, b, a] = rgba.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        Self { r, g, b, a }
+    }
+
+    /// hue in degrees, saturation, value and alpha
+    pub fn to_hsv(&self) -> [GMFloat; 4] {
+        let [r, g, b, a] = self.to_rgba();
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let s = if max > 0.0 { delta / max } else { 0.0 };
+        [hue(r, g, b, max, delta), s, max, a]
+    }
+
+    pub fn from_hsv(hsva: [GMFloat; 4]) -> Self {
+        let [h, s, v, a] = hsva;
+        let c = v * s;
+        Self::from_hue_chroma(h, c, v - c, a)
+    }
+
+    /// hue in degrees, saturation, lightness and alpha
+    pub fn to_hsl(&self) -> [GMFloat; 4] {
+        let [r, g, b, a] = self.to_rgba();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+        let s = if delta > 0.0 {
+            delta / (1.0 - (2.0 * l - 1.0).abs())
+        } else {
+            0.0
+        };
+        [hue(r, g, b, max, delta), s, l, a]
+    }
+
+    pub fn from_hsl(hsla: [GMFloat; 4]) -> Self {
+        let [h, s, l, a] = hsla;
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_hue_chroma(h, c, l - c / 2.0, a)
+    }
+
+    /// shared tail of the hsv and hsl conversions, `m` is added to every channel
+    fn from_hue_chroma(h: GMFloat, c: GMFloat, m: GMFloat, a: GMFloat) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        Self::from_rgba([r + m, g + m, b + m, a])
+    }
+
+    /// srgb transfer function removed, alpha unchanged
+    pub fn to_linear(&self) -> [GMFloat; 4] {
+        let [r, g, b, a] = self.to_rgba();
+        let f = |c: GMFloat| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        [f(r), f(g), f(b), a]
+    }
+
+    pub fn from_linear(rgba: [GMFloat; 4]) -> Self {
+        let [r, g, b, a] = rgba;
+        let f = |c: GMFloat| {
+            let c = c.clamp(0.0, 1.0);
+            if c <= 0.0031308 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            }
+        };
+        Self::from_rgba([f(r), f(g), f(b), a])
+    }
+
+    /// lightness, a, b and alpha in the oklab space
+    pub fn to_oklab(&self) -> [GMFloat; 4] {
+        let [r, g, b, a] = self.to_linear();
+        let l = (0.41222146 * r + 0.53633255 * g + 0.051445995 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+        let s = (0.08830246 * r + 0.28171885 * g + 0.6299787 * b).cbrt();
+        [
+            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+            a,
+        ]
+    }
+
+    pub fn from_oklab(laba: [GMFloat; 4]) -> Self {
+        let [l, a, b, alpha] = laba;
+        let l_ = (l + 0.39633778 * a + 0.21580376 * b).powi(3);
+        let m_ = (l - 0.105561346 * a - 0.06385417 * b).powi(3);
+        let s_ = (l - 0.08948418 * a - 1.2914855 * b).powi(3);
+        Self::from_linear([
+            4.0767417 * l_ - 3.3077116 * m_ + 0.23096994 * s_,
+            -1.268438 * l_ + 2.6097574 * m_ - 0.34131938 * s_,
+            -0.0041960864 * l_ - 0.7034186 * m_ + 1.7076147 * s_,
+            alpha,
+        ])
+    }
+
+    /// `self` at `t == 0`, `other` at `t == 1`, mixed in `space`; hues take the
+    /// short way around the circle
+    pub fn interpolate(&self, other: &Color, t: GMFloat, space: ColorSpace) -> Self {
+        let lerp =
+            |x: [GMFloat; 4], y: [GMFloat; 4]| [0, 1, 2, 3].map(|i| x[i] + (y[i] - x[i]) * t);
+        let lerp_hue = |mut x: [GMFloat; 4], mut y: [GMFloat; 4]| {
+            // a gray has no hue, keep the other one
+            if x[1] == 0.0 {
+                x[0] = y[0];
+            } else if y[1] == 0.0 {
+                y[0] = x[0];
+            }
+            let delta = (y[0] - x[0] + 180.0).rem_euclid(360.0) - 180.0;
+            let mut mixed = lerp(x, y);
+            mixed[0] = x[0] + delta * t;
+            mixed
+        };
+        match space {
+            ColorSpace::Srgb => Self::from_rgba(lerp(self.to_rgba(), other.to_rgba())),
+            ColorSpace::LinearRgb => Self::from_linear(lerp(self.to_linear(), other.to_linear())),
+            ColorSpace::Hsv => Self::from_hsv(lerp_hue(self.to_hsv(), other.to_hsv())),
+            ColorSpace::Hsl => Self::from_hsl(lerp_hue(self.to_hsl(), other.to_hsl())),
+            ColorSpace::Oklab => Self::from_oklab(lerp(self.to_oklab(), other.to_oklab())),
+        }
+    }
+}
+
+/// hue in degrees of an rgb color with the given max channel and chroma
+fn hue(r: GMFloat, g: GMFloat, b: GMFloat, max: GMFloat, delta: GMFloat) -> GMFloat {
+    if delta <= 0.0 {
+        return 0.0;
+    }
+    let h = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    h * 60.0
+}
+
+/// space [`Color::interpolate`] mixes in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    #[default]
+    Srgb,
+    LinearRgb,
+    Hsv,
+    Hsl,
+    Oklab,
+}
+
+impl From<Color> for tiny_skia::Color {
+    fn from(value: Color) -> Self {
+        Self::from_rgba8(value.r, value.g, value.b, value.a)
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::rgb(0x33, 0xcc, 0xff)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorParseError {
+    InvalidHex(String),
+    UnknownName(String),
+}
+
+impl Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorParseError::InvalidHex(s) => write!(f, "\"{}\" is not a hex color", s),
+            ColorParseError::UnknownName(s) => write!(f, "no color named \"{}\"", s),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// hex when it starts with `#`, a css name otherwise
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('#') {
+            Self::from_hex(s)
+        } else {
+            Self::from_css_name(s)
+        }
+    }
+}
+
+// the manim palette
+pub const WHITE: Color = Color::rgb(0xff, 0xff, 0xff);
+pub const BLACK: Color = Color::rgb(0x00, 0x00, 0x00);
+pub const GRAY_A: Color = Color::rgb(0xdd, 0xdd, 0xdd);
+pub const GRAY_B: Color = Color::rgb(0xbb, 0xbb, 0xbb);
+pub const GRAY_C: Color = Color::rgb(0x88, 0x88, 0x88);
+pub const GRAY_D: Color = Color::rgb(0x44, 0x44, 0x44);
+pub const GRAY_E: Color = Color::rgb(0x22, 0x22, 0x22);
+pub const GREY_A: Color = GRAY_A;
+pub const GREY_B: Color = GRAY_B;
+pub const GREY_C: Color = GRAY_C;
+pub const GREY_D: Color = GRAY_D;
+pub const GREY_E: Color = GRAY_E;
+pub const LIGHT_GRAY: Color = GRAY_B;
+pub const GRAY: Color = GRAY_C;
+pub const GREY: Color = GRAY_C;
+pub const DARK_GRAY: Color = GRAY_D;
+pub const DARKER_GRAY: Color = GRAY_E;
+pub const PURE_RED: Color = Color::rgb(0xff, 0x00, 0x00);
+pub const PURE_GREEN: Color = Color::rgb(0x00, 0xff, 0x00);
+pub const PURE_BLUE: Color = Color::rgb(0x00, 0x00, 0xff);
+pub const BLUE_A: Color = Color::rgb(0xc7, 0xe9, 0xf1);
+pub const BLUE_B: Color = Color::rgb(0x9c, 0xdc, 0xeb);
+pub const BLUE_C: Color = Color::rgb(0x58, 0xc4, 0xdd);
+pub const BLUE_D: Color = Color::rgb(0x29, 0xab, 0xca);
+pub const BLUE_E: Color = Color::rgb(0x23, 0x6b, 0x8e);
+pub const TEAL_A: Color = Color::rgb(0xac, 0xea, 0xd7);
+pub const TEAL_B: Color = Color::rgb(0x76, 0xdd, 0xc0);
+pub const TEAL_C: Color = Color::rgb(0x5c, 0xd0, 0xb3);
+pub const TEAL_D: Color = Color::rgb(0x55, 0xc1, 0xa7);
+pub const TEAL_E: Color = Color::rgb(0x49, 0xa8, 0x8f);
+pub const GREEN_A: Color = Color::rgb(0xc9, 0xe2, 0xae);
+pub const GREEN_B: Color = Color::rgb(0xa6, 0xcf, 0x8c);
+pub const GREEN_C: Color = Color::rgb(0x83, 0xc1, 0x67);
+pub const GREEN_D: Color = Color::rgb(0x77, 0xb0, 0x5d);
+pub const GREEN_E: Color = Color::rgb(0x69, 0x9c, 0x52);
+pub const YELLOW_A: Color = Color::rgb(0xff, 0xf1, 0xb6);
+pub const YELLOW_B: Color = Color::rgb(0xff, 0xea, 0x94);
+pub const YELLOW_C: Color = Color::rgb(0xff, 0xff, 0x00);
+pub const YELLOW_D: Color = Color::rgb(0xf4, 0xd3, 0x45);
+pub const YELLOW_E: Color = Color::rgb(0xe8, 0xc1, 0x1c);
+pub const GOLD_A: Color = Color::rgb(0xf7, 0xc7, 0x97);
+pub const GOLD_B: Color = Color::rgb(0xf9, 0xb7, 0x75);
+pub const GOLD_C: Color = Color::rgb(0xf0, 0xac, 0x5f);
+pub const GOLD_D: Color = Color::rgb(0xe1, 0xa1, 0x58);
+pub const GOLD_E: Color = Color::rgb(0xc7, 0x8d, 0x46);
+pub const RED_A: Color = Color::rgb(0xf7, 0xa1, 0xa3);
+pub const RED_B: Color = Color::rgb(0xff, 0x86, 0x66);
+pub const RED_C: Color = Color::rgb(0xfc, 0x62, 0x55);
+pub const RED_D: Color = Color::rgb(0xe6, 0x5a, 0x4c);
+pub const RED_E: Color = Color::rgb(0xcf, 0x50, 0x44);
+pub const MAROON_A: Color = Color::rgb(0xec, 0xab, 0xc1);
+pub const MAROON_B: Color = Color::rgb(0xec, 0x92, 0xab);
+pub const MAROON_C: Color = Color::rgb(0xc5, 0x5f, 0x73);
+pub const MAROON_D: Color = Color::rgb(0xa2, 0x4d, 0x61);
+pub const MAROON_E: Color = Color::rgb(0x94, 0x42, 0x4f);
+pub const PURPLE_A: Color = Color::rgb(0xca, 0xa3, 0xe8);
+pub const PURPLE_B: Color = Color::rgb(0xb1, 0x89, 0xc6);
+pub const PURPLE_C: Color = Color::rgb(0x9a, 0x72, 0xac);
+pub const PURPLE_D: Color = Color::rgb(0x71, 0x55, 0x82);
+pub const PURPLE_E: Color = Color::rgb(0x64, 0x41, 0x72);
+pub const PINK: Color = Color::rgb(0xd1, 0x47, 0xbd);
+pub const LIGHT_PINK: Color = Color::rgb(0xdc, 0x75, 0xcd);
+pub const ORANGE: Color = Color::rgb(0xff, 0x86, 0x2f);
+pub const LIGHT_BROWN: Color = Color::rgb(0xcd, 0xab, 0x7e);
+pub const DARK_BROWN: Color = Color::rgb(0x8b, 0x45, 0x13);
+pub const GRAY_BROWN: Color = Color::rgb(0x73, 0x63, 0x57);
+pub const GREY_BROWN: Color = GRAY_BROWN;
+pub const BLUE: Color = BLUE_C;
+pub const TEAL: Color = TEAL_C;
+pub const GREEN: Color = GREEN_C;
+pub const YELLOW: Color = YELLOW_C;
+pub const GOLD: Color = GOLD_C;
+pub const RED: Color = RED_C;
+pub const MAROON: Color = MAROON_C;
+pub const PURPLE: Color = PURPLE_C;
+
+const CSS_COLORS: [(&str, &str); 149] = [
+    ("aliceblue", "#f0f8ff"),
+    ("antiquewhite", "#faebd7"),
+    ("aqua", "#00ffff"),
+    ("aquamarine", "#7fffd4"),
+    ("azure", "#f0ffff"),
+    ("beige", "#f5f5dc"),
+    ("bisque", "#ffe4c4"),
+    ("black", "#000000"),
+    ("blanchedalmond", "#ffebcd"),
+    ("blue", "#0000ff"),
+    ("blueviolet", "#8a2be2"),
+    ("brown", "#a52a2a"),
+    ("burlywood", "#deb887"),
+    ("cadetblue", "#5f9ea0"),
+    ("chartreuse", "#7fff00"),
+    ("chocolate", "#d2691e"),
+    ("coral", "#ff7f50"),
+    ("cornflowerblue", "#6495ed"),
+    ("cornsilk", "#fff8dc"),
+    ("crimson", "#dc143c"),
+    ("cyan", "#00ffff"),
+    ("darkblue", "#00008b"),
+    ("darkcyan", "#008b8b"),
+    ("darkgoldenrod", "#b8860b"),
+    ("darkgray", "#a9a9a9"),
+    ("darkgreen", "#006400"),
+    ("darkgrey", "#a9a9a9"),
+    ("darkkhaki", "#bdb76b"),
+    ("darkmagenta", "#8b008b"),
+    ("darkolivegreen", "#556b2f"),
+    ("darkorange", "#ff8c00"),
+    ("darkorchid", "#9932cc"),
+    ("darkred", "#8b0000"),
+    ("darksalmon", "#e9967a"),
+    ("darkseagreen", "#8fbc8f"),
+    ("darkslateblue", "#483d8b"),
+    ("darkslategray", "#2f4f4f"),
+    ("darkslategrey", "#2f4f4f"),
+    ("darkturquoise", "#00ced1"),
+    ("darkviolet", "#9400d3"),
+    ("deeppink", "#ff1493"),
+    ("deepskyblue", "#00bfff"),
+    ("dimgray", "#696969"),
+    ("dimgrey", "#696969"),
+    ("dodgerblue", "#1e90ff"),
+    ("firebrick", "#b22222"),
+    ("floralwhite", "#fffaf0"),
+    ("forestgreen", "#228b22"),
+    ("fuchsia", "#ff00ff"),
+    ("gainsboro", "#dcdcdc"),
+    ("ghostwhite", "#f8f8ff"),
+    ("gold", "#ffd700"),
+    ("goldenrod", "#daa520"),
+    ("gray", "#808080"),
+    ("green", "#008000"),
+    ("greenyellow", "#adff2f"),
+    ("grey", "#808080"),
+    ("honeydew", "#f0fff0"),
+    ("hotpink", "#ff69b4"),
+    ("indianred", "#cd5c5c"),
+    ("indigo", "#4b0082"),
+    ("ivory", "#fffff0"),
+    ("khaki", "#f0e68c"),
+    ("lavender", "#e6e6fa"),
+    ("lavenderblush", "#fff0f5"),
+    ("lawngreen", "#7cfc00"),
+    ("lemonchiffon", "#fffacd"),
+    ("lightblue", "#add8e6"),
+    ("lightcoral", "#f08080"),
+    ("lightcyan", "#e0ffff"),
+    ("lightgoldenrodyellow", "#fafad2"),
+    ("lightgray", "#d3d3d3"),
+    ("lightgreen", "#90ee90"),
+    ("lightgrey", "#d3d3d3"),
+    ("lightpink", "#ffb6c1"),
+    ("lightsalmon", "#ffa07a"),
+    ("lightseagreen", "#20b2aa"),
+    ("lightskyblue", "#87cefa"),
+    ("lightslategray", "#778899"),
+    ("lightslategrey", "#778899"),
+    ("lightsteelblue", "#b0c4de"),
+    ("lightyellow", "#ffffe0"),
+    ("lime", "#00ff00"),
+    ("limegreen", "#32cd32"),
+    ("linen", "#faf0e6"),
+    ("magenta", "#ff00ff"),
+    ("maroon", "#800000"),
+    ("mediumaquamarine", "#66cdaa"),
+    ("mediumblue", "#0000cd"),
+    ("mediumorchid", "#ba55d3"),
+    ("mediumpurple", "#9370db"),
+    ("mediumseagreen", "#3cb371"),
+    ("mediumslateblue", "#7b68ee"),
+    ("mediumspringgreen", "#00fa9a"),
+    ("mediumturquoise", "#48d1cc"),
+    ("mediumvioletred", "#c71585"),
+    ("midnightblue", "#191970"),
+    ("mintcream", "#f5fffa"),
+    ("mistyrose", "#ffe4e1"),
+    ("moccasin", "#ffe4b5"),
+    ("navajowhite", "#ffdead"),
+    ("navy", "#000080"),
+    ("oldlace", "#fdf5e6"),
+    ("olive", "#808000"),
+    ("olivedrab", "#6b8e23"),
+    ("orange", "#ffa500"),
+    ("orangered", "#ff4500"),
+    ("orchid", "#da70d6"),
+    ("palegoldenrod", "#eee8aa"),
+    ("palegreen", "#98fb98"),
+    ("paleturquoise", "#afeeee"),
+    ("palevioletred", "#db7093"),
+    ("papayawhip", "#ffefd5"),
+    ("peachpuff", "#ffdab9"),
+    ("peru", "#cd853f"),
+    ("pink", "#ffc0cb"),
+    ("plum", "#dda0dd"),
+    ("powderblue", "#b0e0e6"),
+    ("purple", "#800080"),
+    ("rebeccapurple", "#663399"),
+    ("red", "#ff0000"),
+    ("rosybrown", "#bc8f8f"),
+    ("royalblue", "#4169e1"),
+    ("saddlebrown", "#8b4513"),
+    ("salmon", "#fa8072"),
+    ("sandybrown", "#f4a460"),
+    ("seagreen", "#2e8b57"),
+    ("seashell", "#fff5ee"),
+    ("sienna", "#a0522d"),
+    ("silver", "#c0c0c0"),
+    ("skyblue", "#87ceeb"),
+    ("slateblue", "#6a5acd"),
+    ("slategray", "#708090"),
+    ("slategrey", "#708090"),
+    ("snow", "#fffafa"),
+    ("springgreen", "#00ff7f"),
+    ("steelblue", "#4682b4"),
+    ("tan", "#d2b48c"),
+    ("teal", "#008080"),
+    ("thistle", "#d8bfd8"),
+    ("tomato", "#ff6347"),
+    ("transparent", "#00000000"),
+    ("turquoise", "#40e0d0"),
+    ("violet", "#ee82ee"),
+    ("wheat", "#f5deb3"),
+    ("white", "#ffffff"),
+    ("whitesmoke", "#f5f5f5"),
+    ("yellow", "#ffff00"),
+    ("yellowgreen", "#9acd32"),
+];
+
+#[test]
+fn test_color() {
+    assert_eq!("#58C4DD".parse::<Color>(), Ok(BLUE));
+    assert_eq!(Color::from_hex("f00"), Ok(PURE_RED));
+    assert_eq!(
+        Color::from_hex("#11223344"),
+        Ok(Color::new(0x11, 0x22, 0x33, 0x44))
+    );
+    assert_eq!("RebeccaPurple".parse(), Ok(Color::rgb(0x66, 0x33, 0x99)));
+    assert!("#12345".parse::<Color>().is_err());
+    assert!("notacolor".parse::<Color>().is_err());
+
+    // every conversion gets the color back
+    for color in [
+        BLUE_E,
+        GOLD_A,
+        MAROON_D,
+        GRAY_C,
+        WHITE,
+        BLACK,
+        Color::default(),
+    ] {
+        assert_eq!(Color::from_hsv(color.to_hsv()), color);
+        assert_eq!(Color::from_hsl(color.to_hsl()), color);
+        assert_eq!(Color::from_linear(color.to_linear()), color);
+        assert_eq!(Color::from_oklab(color.to_oklab()), color);
+    }
+    assert_eq!(PURE_RED.to_hsv(), [0.0, 1.0, 1.0, 1.0]);
+    assert_eq!(PURE_BLUE.to_hsl(), [240.0, 1.0, 0.5, 1.0]);
+
+    for space in [
+        ColorSpace::Srgb,
+        ColorSpace::LinearRgb,
+        ColorSpace::Hsv,
+        ColorSpace::Hsl,
+        ColorSpace::Oklab,
+    ] {
+        assert_eq!(RED.interpolate(&BLUE, 0.0, space), RED);
+        assert_eq!(RED.interpolate(&BLUE, 1.0, space), BLUE);
+    }
+    let mid = PURE_RED.interpolate(&PURE_GREEN, 0.5, ColorSpace::Srgb);
+    assert_eq!(mid, Color::rgb(0x80, 0x80, 0x00));
+    // hsv keeps full saturation halfway
+    let mid = PURE_RED.interpolate(&PURE_GREEN, 0.5, ColorSpace::Hsv);
+    assert_eq!(mid, Color::rgb(0xff, 0xff, 0x00));
+    // red to magenta goes through 330 degrees, not through green
+    let mid = PURE_RED.interpolate(&Color::rgb(0xff, 0x00, 0xff), 0.5, ColorSpace::Hsl);
+    assert_eq!(mid.to_hsl()[0].round(), 330.0);
+}
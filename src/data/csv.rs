@@ -0,0 +1,182 @@
+use super::{DataError, DataTable, DataValue};
+
+/// a raw csv field, quoted fields keep their text exactly as written
+struct RawField {
+    text: String,
+    quoted: bool,
+}
+
+impl RawField {
+    fn value(&self) -> DataValue {
+        if self.quoted && self.text.trim() != self.text {
+            DataValue::Text(self.text.clone())
+        } else {
+            DataValue::infer(&self.text)
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        !self.quoted && self.text.trim().is_empty()
+    }
+}
+
+/// split csv text into records of raw fields, each tagged with the line it starts on
+fn split_records(text: &str) -> Result<Vec<(usize, Vec<RawField>)>, DataError> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quote_line = 1;
+    let mut chars = text.chars().peekable();
+    let end_field = |field: &mut String, quoted: &mut bool| RawField {
+        text: std::mem::take(field),
+        quoted: std::mem::take(quoted),
+    };
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if !quoted && field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+                in_quotes = true;
+                quote_line = line;
+            }
+            ',' => fields.push(end_field(&mut field, &mut quoted)),
+            '\r' => {}
+            '\n' => {
+                fields.push(end_field(&mut field, &mut quoted));
+                // blank lines are not records
+                if fields.len() > 1 || !fields[0].is_blank() {
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                record_line = line;
+            }
+            // spaces between the closing quote and the comma belong to no cell
+            c if quoted && c.is_whitespace() => {}
+            c if quoted => {
+                return Err(DataError::InvalidRow {
+                    row: record_line,
+                    message: format!("unexpected '{}' after a closing quote on line {}", c, line),
+                });
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(DataError::Syntax {
+            line: quote_line,
+            message: "unterminated quoted field".to_owned(),
+        });
+    }
+    fields.push(end_field(&mut field, &mut quoted));
+    if fields.len() > 1 || !fields[0].is_blank() {
+        records.push((record_line, fields));
+    }
+    Ok(records)
+}
+
+/// the first record is the header, cells of the others get their types inferred
+pub fn parse_csv(text: &str) -> Result<DataTable, DataError> {
+    let mut records = split_records(text)?.into_iter();
+    let columns: Vec<String> = match records.next() {
+        Some((_, header)) => header
+            .into_iter()
+            .map(|h| {
+                if h.quoted {
+                    h.text
+                } else {
+                    h.text.trim().to_owned()
+                }
+            })
+            .collect(),
+        None => return Ok(DataTable::default()),
+    };
+    let mut table = DataTable {
+        columns,
+        ..Default::default()
+    };
+    for (line, fields) in records {
+        if fields.len() > table.columns.len() {
+            return Err(DataError::InvalidRow {
+                row: line,
+                message: format!(
+                    "{} fields but the header has {} columns",
+                    fields.len(),
+                    table.columns.len()
+                ),
+            });
+        }
+        let mut row: Vec<DataValue> = fields.iter().map(RawField::value).collect();
+        row.resize(table.columns.len(), DataValue::Empty);
+        table.rows.push(row);
+        table.row_numbers.push(line);
+    }
+    Ok(table)
+}
+
+#[test]
+fn test_parse_csv() {
+    use super::ColumnType;
+    let table = parse_csv(
+        "year, sales ,region,ok\n2020,1.5,\"north, east\",true\n\n2021,2,\"say \"\"hi\"\"\"\n2022,n/a,south,false\n",
+    )
+    .unwrap();
+    assert_eq!(table.columns, vec!["year", "sales", "region", "ok"]);
+    assert_eq!(table.rows.len(), 3);
+    assert_eq!(table.row_numbers, vec![2, 4, 5]);
+    assert_eq!(table.rows[0][2], DataValue::Text("north, east".to_owned()));
+    assert_eq!(table.rows[1][2], DataValue::Text("say \"hi\"".to_owned()));
+    assert_eq!(table.rows[1][3], DataValue::Empty);
+    assert_eq!(table.column_type("year").unwrap(), ColumnType::Number);
+    assert_eq!(table.column_type("sales").unwrap(), ColumnType::Text);
+    assert_eq!(table.column_type("ok").unwrap(), ColumnType::Bool);
+    assert_eq!(table.numbers("year").unwrap(), vec![2020.0, 2021.0, 2022.0]);
+    match table.numbers("sales") {
+        Err(DataError::TypeMismatch { row, .. }) => assert_eq!(row, 5),
+        _ => panic!("expected a type mismatch"),
+    }
+    let selected = table.select(&["region", "year"]).unwrap();
+    assert_eq!(selected.rows[2][1], DataValue::Number(2022.0));
+    assert!(matches!(
+        parse_csv("a,b\n1,2,3\n"),
+        Err(DataError::InvalidRow { row: 2, .. })
+    ));
+    assert!(matches!(
+        parse_csv("a,b\n1,\"2\n"),
+        Err(DataError::Syntax { line: 2, .. })
+    ));
+    // text after a closing quote is an error, whitespace inside the quotes is kept
+    match parse_csv("a,b\n1,2\n3,\"4\"5\n") {
+        Err(DataError::InvalidRow { row, message }) => {
+            assert_eq!(row, 3);
+            assert!(message.contains("line 3"));
+        }
+        _ => panic!("expected an invalid row"),
+    }
+    let quoted = parse_csv("a,b\n\"  padded \" ,\" \"\n").unwrap();
+    assert_eq!(quoted.rows[0][0], DataValue::Text("  padded ".to_owned()));
+    assert_eq!(quoted.rows[0][1], DataValue::Text(" ".to_owned()));
+    // spaces after the commas belong to no cell
+    let spaced = parse_csv("label, value\nnorth, 1\n south, 2").unwrap();
+    assert_eq!(spaced.texts("label").unwrap(), vec!["north", "south"]);
+    assert_eq!(spaced.numbers("value").unwrap(), vec![1.0, 2.0]);
+}
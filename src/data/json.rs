@@ -0,0 +1,321 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::GMFloat;
+
+use super::{DataError, DataTable, DataValue};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+struct JsonParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, DataError> {
+        Err(DataError::Syntax {
+            line: self.line,
+            message: message.to_owned(),
+        })
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+            self.next_char();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), DataError> {
+        self.skip_whitespace();
+        match self.next_char() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => self.error(&format!("expected '{}', found '{}'", expected, c)),
+            None => self.error(&format!("expected '{}', found end of file", expected)),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, DataError> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(c) if *c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(_) => self.parse_keyword(),
+            None => self.error("unexpected end of file"),
+        }
+    }
+
+    fn parse_keyword(&mut self) -> Result<JsonValue, DataError> {
+        let mut word = String::new();
+        while matches!(self.chars.peek(), Some(c) if c.is_ascii_alphabetic()) {
+            word.push(self.next_char().unwrap());
+        }
+        match word.as_str() {
+            "null" => Ok(JsonValue::Null),
+            "true" => Ok(JsonValue::Bool(true)),
+            "false" => Ok(JsonValue::Bool(false)),
+            _ => self.error(&format!("unexpected token \"{}\"", word)),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, DataError> {
+        let mut number = String::new();
+        while matches!(self.chars.peek(), Some(c) if c.is_ascii_digit() || "+-.eE".contains(*c)) {
+            number.push(self.next_char().unwrap());
+        }
+        match number.parse::<f64>() {
+            Ok(n) => Ok(JsonValue::Number(n)),
+            Err(_) => self.error(&format!("invalid number \"{}\"", number)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, DataError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.next_char() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.next_char() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some('r') => s.push('\r'),
+                    Some('b') => s.push('\u{8}'),
+                    Some('f') => s.push('\u{c}'),
+                    Some('u') => {
+                        let hex = self.unicode_escape()?;
+                        let code = match hex {
+                            // a high surrogate has to be followed by an escaped low surrogate
+                            0xd800..=0xdbff => {
+                                if self.next_char() != Some('\\') || self.next_char() != Some('u') {
+                                    return self.error("unpaired surrogate in \\u escape");
+                                }
+                                match self.unicode_escape()? {
+                                    low @ 0xdc00..=0xdfff => {
+                                        0x10000 + ((hex - 0xd800) << 10) + (low - 0xdc00)
+                                    }
+                                    _ => return self.error("unpaired surrogate in \\u escape"),
+                                }
+                            }
+                            _ => hex,
+                        };
+                        match char::from_u32(code) {
+                            Some(c) => s.push(c),
+                            None => return self.error(&format!("invalid escape \\u{:04x}", code)),
+                        }
+                    }
+                    Some(c) => s.push(c),
+                    None => return self.error("unterminated string"),
+                },
+                Some(c) => s.push(c),
+                None => return self.error("unterminated string"),
+            }
+        }
+    }
+
+    /// the four hex digits after a \u
+    fn unicode_escape(&mut self) -> Result<u32, DataError> {
+        let hex: String = (0..4).filter_map(|_| self.next_char()).collect();
+        match u32::from_str_radix(&hex, 16) {
+            Ok(code) if hex.len() == 4 => Ok(code),
+            _ => self.error(&format!("invalid escape \\u{}", hex)),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, DataError> {
+        self.expect('[')?;
+        let mut items = vec![];
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&']') {
+            self.next_char();
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.next_char() {
+                Some(',') => {}
+                Some(']') => return Ok(JsonValue::Array(items)),
+                _ => return self.error("expected ',' or ']' in array"),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, DataError> {
+        self.expect('{')?;
+        let mut members = vec![];
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'}') {
+            self.next_char();
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            members.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.next_char() {
+                Some(',') => {}
+                Some('}') => return Ok(JsonValue::Object(members)),
+                _ => return self.error("expected ',' or '}' in object"),
+            }
+        }
+    }
+}
+
+pub fn parse_json(text: &str) -> Result<JsonValue, DataError> {
+    let mut parser = JsonParser {
+        chars: text.chars().peekable(),
+        line: 1,
+    };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.chars.peek().is_some() {
+        return parser.error("trailing characters after json value");
+    }
+    Ok(value)
+}
+
+fn to_data_value(value: &JsonValue, row: usize) -> Result<DataValue, DataError> {
+    match value {
+        JsonValue::Null => Ok(DataValue::Empty),
+        JsonValue::Bool(b) => Ok(DataValue::Bool(*b)),
+        JsonValue::Number(n) => Ok(DataValue::Number(*n as GMFloat)),
+        JsonValue::String(s) => Ok(DataValue::Text(s.clone())),
+        _ => Err(DataError::InvalidRow {
+            row,
+            message: "nested arrays and objects can't be table cells".to_owned(),
+        }),
+    }
+}
+
+/// accept either an array of records `[{"x": 1, "y": 2}, ...]`
+/// or an object of columns `{"x": [1, ...], "y": [2, ...]}`
+pub fn parse_json_table(text: &str) -> Result<DataTable, DataError> {
+    let mut table = DataTable::default();
+    match parse_json(text)? {
+        JsonValue::Array(records) => {
+            for (i, record) in records.iter().enumerate() {
+                let row_number = i + 1;
+                let JsonValue::Object(members) = record else {
+                    return Err(DataError::InvalidRow {
+                        row: row_number,
+                        message: "expected an object".to_owned(),
+                    });
+                };
+                let mut row = vec![DataValue::Empty; table.columns.len()];
+                for (key, value) in members {
+                    let index = match table.columns.iter().position(|c| c == key) {
+                        Some(index) => index,
+                        None => {
+                            // a column first seen here is empty in all previous rows
+                            table.columns.push(key.clone());
+                            table.rows.iter_mut().for_each(|r| r.push(DataValue::Empty));
+                            row.push(DataValue::Empty);
+                            table.columns.len() - 1
+                        }
+                    };
+                    row[index] = to_data_value(value, row_number)?;
+                }
+                table.rows.push(row);
+                table.row_numbers.push(row_number);
+            }
+        }
+        JsonValue::Object(columns) => {
+            let row_count = columns
+                .iter()
+                .map(|(_, c)| match c {
+                    JsonValue::Array(items) => items.len(),
+                    _ => 0,
+                })
+                .max()
+                .unwrap_or(0);
+            table.rows = vec![vec![]; row_count];
+            table.row_numbers = (1..=row_count).collect();
+            for (name, column) in &columns {
+                let JsonValue::Array(items) = column else {
+                    return Err(DataError::NotATable(format!(
+                        "column \"{}\" is not an array",
+                        name
+                    )));
+                };
+                table.columns.push(name.clone());
+                for (i, row) in table.rows.iter_mut().enumerate() {
+                    row.push(match items.get(i) {
+                        Some(v) => to_data_value(v, i + 1)?,
+                        None => DataValue::Empty,
+                    });
+                }
+            }
+        }
+        _ => {
+            return Err(DataError::NotATable(
+                "expected an array of records or an object of columns".to_owned(),
+            ))
+        }
+    }
+    Ok(table)
+}
+
+#[test]
+fn test_parse_json_table() {
+    let records = parse_json_table(
+        r#"[{"x": 1, "y": -2.5e1, "name": "a\"b"},
+            {"x": 2, "y": null, "extra": true}]"#,
+    )
+    .unwrap();
+    assert_eq!(records.columns, vec!["x", "y", "name", "extra"]);
+    assert_eq!(records.rows[0][1], DataValue::Number(-25.0));
+    assert_eq!(records.rows[0][2], DataValue::Text("a\"b".to_owned()));
+    assert_eq!(records.rows[0][3], DataValue::Empty);
+    assert_eq!(records.rows[1][3], DataValue::Bool(true));
+    match records.numbers("y") {
+        Err(DataError::TypeMismatch { row, .. }) => assert_eq!(row, 2),
+        _ => panic!("expected a type mismatch"),
+    }
+
+    let columns = parse_json_table(r#"{"x": [0, 1, 2], "y": [0, 1, 4]}"#).unwrap();
+    let points = columns.points("x", "y").unwrap();
+    assert_eq!(points[2], crate::GMPoint::new(2.0, 4.0, 0.0));
+
+    let emoji = parse_json_table(r#"[{"s": "\ud83d\ude00 \u00e9"}]"#).unwrap();
+    assert_eq!(
+        emoji.rows[0][0],
+        DataValue::Text("\u{1f600} \u{e9}".to_owned())
+    );
+    assert!(matches!(
+        parse_json_table(r#"[{"s": "\ud83d"}]"#),
+        Err(DataError::Syntax { .. })
+    ));
+
+    assert!(matches!(
+        parse_json_table("[{\"x\": 1},\n {\"x\": [1]}]"),
+        Err(DataError::InvalidRow { row: 2, .. })
+    ));
+    assert!(matches!(
+        parse_json_table("[\n{\"x\": 1}\n{\"x\": 2}]"),
+        Err(DataError::Syntax { line: 3, .. })
+    ));
+    assert!(matches!(
+        parse_json_table("{\n\"x\": [1],\n\"y\": 2}"),
+        Err(DataError::NotATable(_))
+    ));
+}
@@ -0,0 +1,231 @@
+use std::fmt::Display;
+use std::fs;
+
+use nalgebra::Point3;
+
+use crate::{GMFloat, GMPoint};
+
+pub mod csv;
+pub mod json;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataValue {
+    Number(GMFloat),
+    Bool(bool),
+    Text(String),
+    Empty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnType {
+    Number,
+    Bool,
+    Text,
+    Empty,
+}
+
+impl DataValue {
+    /// guess the type of a raw text cell, as read from a csv file
+    pub fn infer(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return DataValue::Empty;
+        }
+        if trimmed.eq_ignore_ascii_case("true") {
+            return DataValue::Bool(true);
+        }
+        if trimmed.eq_ignore_ascii_case("false") {
+            return DataValue::Bool(false);
+        }
+        match trimmed.parse::<GMFloat>() {
+            Ok(n) => DataValue::Number(n),
+            Err(_) => DataValue::Text(trimmed.to_owned()),
+        }
+    }
+
+    pub fn value_type(&self) -> ColumnType {
+        match self {
+            DataValue::Number(_) => ColumnType::Number,
+            DataValue::Bool(_) => ColumnType::Bool,
+            DataValue::Text(_) => ColumnType::Text,
+            DataValue::Empty => ColumnType::Empty,
+        }
+    }
+}
+
+impl Display for DataValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataValue::Number(n) => write!(f, "{}", n),
+            DataValue::Bool(b) => write!(f, "{}", b),
+            DataValue::Text(t) => write!(f, "{}", t),
+            DataValue::Empty => Ok(()),
+        }
+    }
+}
+
+impl Display for ColumnType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnType::Number => write!(f, "number"),
+            ColumnType::Bool => write!(f, "bool"),
+            ColumnType::Text => write!(f, "text"),
+            ColumnType::Empty => write!(f, "empty"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DataError {
+    Io(std::io::Error),
+    /// the file itself is malformed at `line`
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    /// a record has the wrong shape, e.g. too many fields
+    InvalidRow {
+        row: usize,
+        message: String,
+    },
+    /// well formed, but not laid out as a table, e.g. a json string at the top
+    NotATable(String),
+    MissingColumn(String),
+    TypeMismatch {
+        row: usize,
+        column: String,
+        expected: ColumnType,
+        found: DataValue,
+    },
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::Io(e) => write!(f, "can't read data file: {}", e),
+            DataError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            DataError::InvalidRow { row, message } => write!(f, "row {}: {}", row, message),
+            DataError::NotATable(message) => write!(f, "not a table: {}", message),
+            DataError::MissingColumn(name) => write!(f, "no column named \"{}\"", name),
+            DataError::TypeMismatch {
+                row,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {}: expected {} in column \"{}\", found \"{}\"",
+                row, expected, column, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+impl From<std::io::Error> for DataError {
+    fn from(value: std::io::Error) -> Self {
+        DataError::Io(value)
+    }
+}
+
+/// column oriented data loaded from a csv or json file
+#[derive(Debug, Clone, Default)]
+pub struct DataTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<DataValue>>,
+    /// where each row came from, used in error messages: the line of a csv record
+    /// or the 1-based index of a json record
+    pub row_numbers: Vec<usize>,
+}
+
+impl DataTable {
+    pub fn from_csv_file(file_path: &str) -> Result<Self, DataError> {
+        csv::parse_csv(&fs::read_to_string(file_path)?)
+    }
+
+    pub fn from_csv_str(text: &str) -> Result<Self, DataError> {
+        csv::parse_csv(text)
+    }
+
+    pub fn from_json_file(file_path: &str) -> Result<Self, DataError> {
+        json::parse_json_table(&fs::read_to_string(file_path)?)
+    }
+
+    pub fn from_json_str(text: &str) -> Result<Self, DataError> {
+        json::parse_json_table(text)
+    }
+
+    pub fn column_index(&self, column: &str) -> Result<usize, DataError> {
+        self.columns
+            .iter()
+            .position(|c| c == column)
+            .ok_or_else(|| DataError::MissingColumn(column.to_owned()))
+    }
+
+    /// common type of the non empty cells, mixed columns are text
+    pub fn column_type(&self, column: &str) -> Result<ColumnType, DataError> {
+        let index = self.column_index(column)?;
+        let mut column_type = ColumnType::Empty;
+        for row in &self.rows {
+            column_type = match (column_type, row[index].value_type()) {
+                (t, ColumnType::Empty) => t,
+                (ColumnType::Empty, t) => t,
+                (a, b) if a == b => a,
+                _ => ColumnType::Text,
+            };
+        }
+        Ok(column_type)
+    }
+
+    /// keep only `columns`, in the given order
+    pub fn select(&self, columns: &[&str]) -> Result<DataTable, DataError> {
+        let indices = columns
+            .iter()
+            .map(|c| self.column_index(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DataTable {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: self
+                .rows
+                .iter()
+                .map(|r| indices.iter().map(|i| r[*i].clone()).collect())
+                .collect(),
+            row_numbers: self.row_numbers.clone(),
+        })
+    }
+
+    pub fn numbers(&self, column: &str) -> Result<Vec<GMFloat>, DataError> {
+        let index = self.column_index(column)?;
+        self.rows
+            .iter()
+            .zip(&self.row_numbers)
+            .map(|(r, row)| match &r[index] {
+                DataValue::Number(n) => Ok(*n),
+                other => Err(DataError::TypeMismatch {
+                    row: *row,
+                    column: column.to_owned(),
+                    expected: ColumnType::Number,
+                    found: other.clone(),
+                }),
+            })
+            .collect()
+    }
+
+    /// cells of `column` as strings, whatever their type
+    pub fn texts(&self, column: &str) -> Result<Vec<String>, DataError> {
+        let index = self.column_index(column)?;
+        Ok(self.rows.iter().map(|r| r[index].to_string()).collect())
+    }
+
+    /// points (x, y, 0) for plotting one numeric column against another
+    pub fn points(&self, x_column: &str, y_column: &str) -> Result<Vec<GMPoint>, DataError> {
+        let xs = self.numbers(x_column)?;
+        let ys = self.numbers(y_column)?;
+        Ok(xs
+            .into_iter()
+            .zip(ys)
+            .map(|(x, y)| Point3::new(x, y, 0.0))
+            .collect())
+    }
+}
@@ -1,17 +1,24 @@
 #![allow(unused)]
 
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
-use mobjects::{coordinate_change_x, coordinate_change_y};
+use log::error;
+
+use mobjects::arena::{MobjectArena, MobjectId, MobjectKind};
+use mobjects::gradient::Gradient;
+use mobjects::{coordinate_change_x, coordinate_change_y, BoundingBox};
 
 pub mod camera;
 use nalgebra::Point3;
 
 pub mod animation;
+pub mod color;
+pub mod data;
 pub mod log_utils;
 pub mod math_utils;
 pub mod mobjects;
+pub mod theme;
 pub mod video_backend;
 
 cfg_if::cfg_if! {
@@ -27,36 +34,7 @@ cfg_if::cfg_if! {
 }
 
 pub type GMPoint = Point3<GMFloat>;
-#[derive(Clone, Copy, Debug)]
-struct Color {
-    r: u8,
-    g: u8,
-    b: u8,
-    a: u8,
-}
-
-impl Color {
-    fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
-        Self { r, g, b, a }
-    }
-}
-
-impl From<Color> for tiny_skia::Color {
-    fn from(value: Color) -> Self {
-        Self::from_rgba8(value.r, value.g, value.b, value.a)
-    }
-}
-
-impl Default for Color {
-    fn default() -> Self {
-        Self {
-            r: 0x33,
-            g: 0xcc,
-            b: 0xff,
-            a: 0xff,
-        }
-    }
-}
+pub use color::Color;
 
 pub enum ContextType {
     TinySKIA(tiny_skia::Pixmap), // we always have cairo as a fallback
@@ -65,6 +43,7 @@ pub enum ContextType {
     HIP,
 }
 
+#[derive(Debug, Clone)]
 pub struct SceneConfig {
     pub width: GMFloat,
     pub height: GMFloat,
@@ -76,6 +55,18 @@ pub struct SceneConfig {
 pub struct Context {
     pub ctx_type: ContextType,
     pub scene_config: SceneConfig,
+    /// the last background let pixels through, frames are handed out with straight alpha
+    transparent: bool,
+    /// limits everything drawn to where it is set, see `mobjects::mask::Masked`
+    pub mask: Option<tiny_skia::Mask>,
+    /// canvases under the one being drawn on, see `push_layer`
+    layers: Vec<Layer>,
+}
+
+/// a canvas put aside by `Context::push_layer`, with the mask it was drawn with
+struct Layer {
+    pixmap: tiny_skia::Pixmap,
+    mask: Option<tiny_skia::Mask>,
 }
 
 impl SceneConfig{
@@ -85,6 +76,13 @@ impl SceneConfig{
     pub fn convert_coord_y(&self, y: GMFloat) -> GMFloat {
         coordinate_change_y(y, self.height) * self.scale_factor
     }
+    /// the visible part of the scene, centered at origin
+    pub fn frame_bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point3::new(-self.width / 2.0, -self.height / 2.0, 0.0),
+            Point3::new(self.width / 2.0, self.height / 2.0, 0.0),
+        )
+    }
 }
 
 impl Default for SceneConfig {
@@ -107,45 +105,234 @@ impl Default for Context {
         Self {
             ctx_type: ContextType::TinySKIA(pixmap),
             scene_config,
+            transparent: false,
+            mask: None,
+            layers: vec![],
         }
     }
 }
 
 impl Context {
-    fn clear_transparent(&mut self) {
+    /// blank transparent context of the same size, for drawing something on its own
+    pub fn offscreen(&self) -> Context {
+        let pixmap = tiny_skia::Pixmap::new(
+            self.scene_config.output_width,
+            self.scene_config.output_height,
+        )
+        .unwrap();
+        Context {
+            ctx_type: ContextType::TinySKIA(pixmap),
+            scene_config: self.scene_config.clone(),
+            transparent: true,
+            mask: None,
+            layers: vec![],
+        }
+    }
+
+    /// draw on a blank offscreen canvas until the matching `pop_layer`
+    pub fn push_layer(&mut self) {
+        let ContextType::TinySKIA(pixmap) = &mut self.ctx_type else {
+            return;
+        };
+        let Some(blank) = tiny_skia::Pixmap::new(pixmap.width(), pixmap.height()) else {
+            return;
+        };
+        self.layers.push(Layer {
+            pixmap: std::mem::replace(pixmap, blank),
+            mask: self.mask.take(),
+        });
+    }
+
+    /// composite the top layer onto the one below, with `opacity` applied once for
+    /// the whole layer and the mask that was active at `push_layer`
+    pub fn pop_layer(&mut self, opacity: GMFloat, blend_mode: tiny_skia::BlendMode) {
+        let ContextType::TinySKIA(pixmap) = &mut self.ctx_type else {
+            return;
+        };
+        let Some(layer) = self.layers.pop() else {
+            return;
+        };
+        let drawn = std::mem::replace(pixmap, layer.pixmap);
+        self.mask = layer.mask;
+        let paint = tiny_skia::PixmapPaint {
+            opacity: opacity.clamp(0.0, 1.0),
+            blend_mode,
+            quality: tiny_skia::FilterQuality::Nearest,
+        };
+        pixmap.draw_pixmap(
+            0,
+            0,
+            drawn.as_ref(),
+            &paint,
+            tiny_skia::Transform::identity(),
+            self.mask.as_ref(),
+        );
+    }
+
+    fn clear(&mut self, background: &Background) {
+        self.transparent = !background.is_opaque();
         match &mut self.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                pixmap.fill(tiny_skia::Color::from_rgba8(0, 0, 0, 0xff));
-            }
+            ContextType::TinySKIA(pixmap) => match background {
+                Background::Color(color) => pixmap.fill((*color).into()),
+                Background::Image(image) => {
+                    pixmap.fill(tiny_skia::Color::BLACK);
+                    // cover the whole frame, cropping what sticks out
+                    let scale = (pixmap.width() as f32 / image.width() as f32)
+                        .max(pixmap.height() as f32 / image.height() as f32);
+                    let transform = tiny_skia::Transform::from_row(
+                        scale,
+                        0.0,
+                        0.0,
+                        scale,
+                        (pixmap.width() as f32 - image.width() as f32 * scale) / 2.0,
+                        (pixmap.height() as f32 - image.height() as f32 * scale) / 2.0,
+                    );
+                    let paint = tiny_skia::PixmapPaint {
+                        quality: tiny_skia::FilterQuality::Bicubic,
+                        ..Default::default()
+                    };
+                    pixmap.draw_pixmap(0, 0, image.as_ref(), &paint, transform, None);
+                }
+                Background::Gradient(gradient) => {
+                    pixmap.fill(tiny_skia::Color::TRANSPARENT);
+                    let rect = tiny_skia::Rect::from_xywh(
+                        0.0,
+                        0.0,
+                        pixmap.width() as f32,
+                        pixmap.height() as f32,
+                    );
+                    if let (Some(rect), Some(shader)) =
+                        (rect, gradient.shader(1.0, &self.scene_config))
+                    {
+                        let paint = tiny_skia::Paint {
+                            shader,
+                            ..Default::default()
+                        };
+                        pixmap.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+                    }
+                }
+                Background::Transparent => pixmap.fill(tiny_skia::Color::TRANSPARENT),
+            },
             _ => {}
         }
     }
 
-    fn image_bytes(&self) -> &[u8] {
+    /// rgba bytes of the frame, with straight alpha when the background isn't opaque
+    fn image_bytes(&self) -> Cow<'_, [u8]> {
         match &self.ctx_type {
-            ContextType::TinySKIA(pixmap) => pixmap.data(),
-            _ => &[],
+            ContextType::TinySKIA(pixmap) if self.transparent => pixmap
+                .pixels()
+                .iter()
+                .flat_map(|p| {
+                    let c = p.demultiply();
+                    [c.red(), c.green(), c.blue(), c.alpha()]
+                })
+                .collect(),
+            ContextType::TinySKIA(pixmap) => Cow::Borrowed(pixmap.data()),
+            _ => Cow::Borrowed(&[]),
         }
     }
 
 
 }
 
+/// what a scene is drawn on
+#[derive(Debug, Clone)]
+pub enum Background {
+    Color(Color),
+    /// scaled to cover the frame, keeping its aspect ratio
+    Image(tiny_skia::Pixmap),
+    /// placed in scene coordinates like the gradients of mobjects
+    Gradient(Gradient),
+    /// nothing, the frames keep their alpha for compositing in an editor
+    Transparent,
+}
+
+impl Background {
+    /// image background from a png or jpeg file, decoded like an `ImageMobject`
+    pub fn from_image(file_path: &str) -> Result<Self, mobjects::image::ImageError> {
+        let data = std::fs::read(file_path)?;
+        Ok(Background::Image(mobjects::image::decode_image(&data)?))
+    }
+
+    fn is_opaque(&self) -> bool {
+        match self {
+            Background::Color(color) => color.a == 0xff,
+            Background::Image(_) => true,
+            Background::Gradient(gradient) => gradient.stops.iter().all(|s| s.color.a == 0xff),
+            Background::Transparent => false,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Scene {
-    pub mobjects: Vec<Rc<RefCell<Box<dyn mobjects::Mobject>>>>,
+    /// only reachable through the scene, so draw order, z-indices and the rest stay in sync
+    mobjects: MobjectArena,
+    /// the theme background when None
+    pub background: Option<Background>,
+    /// draw mobjects of equal z-index back to front by the z coordinate of their center
+    pub depth_sort: bool,
+    /// run on all mobjects of the frame together, the background stays untouched
+    pub effects: Vec<mobjects::effects::Effect>,
+    /// later ids are drawn on top of earlier ones with the same z-index
+    draw_order: Vec<MobjectId>,
+    z_indices: HashMap<MobjectId, i32>,
+    hidden: HashSet<MobjectId>,
+    saved_states: HashMap<MobjectId, Box<dyn mobjects::Mobject>>,
 }
 
 impl Scene {
     pub fn new() -> Self {
-        Scene { mobjects: vec![] }
+        Scene::default()
     }
-    pub fn save_png(&self, ctx: &mut Context, file_path: &str) {
-        ctx.clear_transparent();
-
-        for m in self.mobjects.iter() {
-            m.borrow().draw(ctx);
+    pub fn render(&self, ctx: &mut Context) {
+        match &self.background {
+            Some(background) => ctx.clear(background),
+            None => ctx.clear(&Background::Color(theme::theme().background)),
+        }
+        let layered = !self.effects.is_empty();
+        if layered {
+            ctx.push_layer();
+        }
+        for id in self.render_order() {
+            if !self.is_visible(id) {
+                continue;
+            }
+            if let Some(m) = self.mobjects.get(id) {
+                m.draw(ctx);
+            }
         }
+        if layered {
+            mobjects::effects::apply_effects(&self.effects, ctx);
+            ctx.pop_layer(1.0, tiny_skia::BlendMode::SourceOver);
+        }
+    }
+
+    /// ids sorted by z-index, then by depth when `depth_sort` is set, then by draw order
+    pub fn render_order(&self) -> Vec<MobjectId> {
+        let depth = |id: &MobjectId| {
+            if !self.depth_sort {
+                return 0.0;
+            }
+            self.mobjects
+                .get(*id)
+                .and_then(|m| m.bounding_box())
+                .map(|b| b.center().z)
+                .unwrap_or(0.0)
+        };
+        let mut order = self.draw_order.clone();
+        // stable, so ties keep their draw order
+        order.sort_by(|a, b| {
+            self.z_index(*a)
+                .cmp(&self.z_index(*b))
+                .then(depth(a).total_cmp(&depth(b)))
+        });
+        order
+    }
+
+    pub fn save_png(&self, ctx: &mut Context, file_path: &str) {
+        self.render(ctx);
 
         match &mut ctx.ctx_type {
             ContextType::TinySKIA(pixmap) => {
@@ -155,11 +342,156 @@ impl Scene {
         }
     }
 
-    pub fn add(&mut self, mobject: Box<dyn mobjects::Mobject>) {
-        self.mobjects.push(Rc::new(RefCell::new(mobject)));
+    pub fn add(&mut self, mobject: Box<dyn mobjects::Mobject>) -> MobjectId {
+        let id = self.mobjects.insert_boxed(mobject);
+        self.draw_order.push(id);
+        id
+    }
+    /// like `add`, but the id keeps the concrete type for `get`/`get_mut`
+    pub fn add_typed<T: mobjects::Mobject + 'static>(&mut self, mobject: T) -> MobjectId<T> {
+        let id = self.mobjects.insert(mobject);
+        self.draw_order.push(id.untyped());
+        id
+    }
+    pub fn remove<T: ?Sized>(&mut self, id: MobjectId<T>) -> Option<Box<dyn mobjects::Mobject>> {
+        let id = id.untyped();
+        self.draw_order.retain(|i| *i != id);
+        self.z_indices.remove(&id);
+        self.hidden.remove(&id);
+        self.saved_states.remove(&id);
+        self.mobjects.remove(id)
+    }
+    pub fn clear(&mut self) {
+        self.mobjects.clear();
+        self.draw_order.clear();
+        self.z_indices.clear();
+        self.hidden.clear();
+        self.saved_states.clear();
+    }
+    /// put `new` where `old` was, keeping its draw order, z-index and visibility;
+    /// None and `new` is dropped when `old` isn't in the scene
+    pub fn replace<T: ?Sized>(
+        &mut self,
+        old: MobjectId<T>,
+        new: Box<dyn mobjects::Mobject>,
+    ) -> Option<MobjectId> {
+        let old = old.untyped();
+        let position = self.draw_order.iter().position(|i| *i == old)?;
+        let z_index = self.z_index(old);
+        let visible = self.is_visible(old);
+        self.remove(old);
+
+        let id = self.mobjects.insert_boxed(new);
+        self.draw_order.insert(position, id);
+        self.set_z_index(id, z_index);
+        self.set_visible(id, visible);
+        Some(id)
+    }
+    /// swap in a copy of `other` under the same id, keeping draw order, z-index and
+    /// visibility; typed ids of the old type stop resolving when the type changes
+    pub fn become_mobject<T: ?Sized>(
+        &mut self,
+        id: MobjectId<T>,
+        other: &dyn mobjects::Mobject,
+    ) -> bool {
+        self.mobjects
+            .replace_boxed(id, other.mobject_clone())
+            .is_some()
+    }
+    /// remember a copy of the mobject for `restore` and the `Restore` animation
+    pub fn save_state<T: ?Sized>(&mut self, id: MobjectId<T>) {
+        if let Some(m) = self.mobjects.get(id.untyped()) {
+            self.saved_states.insert(id.untyped(), m.mobject_clone());
+        }
+    }
+    pub fn saved_state<T: ?Sized>(&self, id: MobjectId<T>) -> Option<&dyn mobjects::Mobject> {
+        self.saved_states.get(&id.untyped()).map(|m| m.as_ref())
+    }
+    /// put the mobject back to its saved state, false when nothing was saved
+    pub fn restore<T: ?Sized>(&mut self, id: MobjectId<T>) -> bool {
+        let Some(saved) = self.saved_states.get(&id.untyped()) else {
+            return false;
+        };
+        let saved = saved.mobject_clone();
+        self.become_mobject(id, saved.as_ref())
+    }
+    /// hidden mobjects stay in the scene but aren't drawn
+    pub fn set_visible<T: ?Sized>(&mut self, id: MobjectId<T>, visible: bool) {
+        if visible {
+            self.hidden.remove(&id.untyped());
+        } else if self.mobjects.contains(id) {
+            self.hidden.insert(id.untyped());
+        }
+    }
+    pub fn is_visible<T: ?Sized>(&self, id: MobjectId<T>) -> bool {
+        !self.hidden.contains(&id.untyped())
+    }
+    /// mobjects with a higher z-index are drawn on top, the default is 0
+    pub fn set_z_index<T: ?Sized>(&mut self, id: MobjectId<T>, z_index: i32) {
+        if self.mobjects.contains(id) {
+            self.z_indices.insert(id.untyped(), z_index);
+        }
+    }
+    pub fn z_index<T: ?Sized>(&self, id: MobjectId<T>) -> i32 {
+        self.z_indices.get(&id.untyped()).copied().unwrap_or(0)
+    }
+    /// draw `id` after everything else, raising its z-index to the highest one in use
+    pub fn bring_to_front<T: ?Sized>(&mut self, id: MobjectId<T>) {
+        let id = id.untyped();
+        if let Some(position) = self.draw_order.iter().position(|i| *i == id) {
+            let top = self.draw_order.iter().map(|i| self.z_index(*i)).max();
+            self.draw_order.remove(position);
+            self.draw_order.push(id);
+            self.set_z_index(id, top.unwrap_or(0));
+        }
+    }
+    /// draw `id` before everything else, lowering its z-index to the lowest one in use
+    pub fn send_to_back<T: ?Sized>(&mut self, id: MobjectId<T>) {
+        let id = id.untyped();
+        if let Some(position) = self.draw_order.iter().position(|i| *i == id) {
+            let bottom = self.draw_order.iter().map(|i| self.z_index(*i)).min();
+            self.draw_order.remove(position);
+            self.draw_order.insert(0, id);
+            self.set_z_index(id, bottom.unwrap_or(0));
+        }
+    }
+    pub fn get<T: MobjectKind + ?Sized>(&self, id: MobjectId<T>) -> Option<&T> {
+        self.mobjects.get(id)
+    }
+    pub fn get_mut<T: MobjectKind + ?Sized>(&mut self, id: MobjectId<T>) -> Option<&mut T> {
+        self.mobjects.get_mut(id)
+    }
+    pub fn contains<T: ?Sized>(&self, id: MobjectId<T>) -> bool {
+        self.mobjects.contains(id)
+    }
+    /// typed handle to the same mobject, if it is a `T`
+    pub fn downcast<T: mobjects::Mobject + 'static>(&self, id: MobjectId) -> Option<MobjectId<T>> {
+        self.mobjects.downcast(id)
+    }
+    pub fn len(&self) -> usize {
+        self.mobjects.len()
     }
-    pub fn add_ref(&mut self, mobject_ref: Rc<RefCell<Box<dyn mobjects::Mobject>>>) {
-        self.mobjects.push(mobject_ref.clone());
+    pub fn is_empty(&self) -> bool {
+        self.mobjects.is_empty()
+    }
+    /// the mobjects with their ids, in the order of `ids`
+    pub fn iter(&self) -> impl Iterator<Item = (MobjectId, &dyn mobjects::Mobject)> + '_ {
+        self.draw_order
+            .iter()
+            .filter_map(|id| Some((*id, self.mobjects.get(*id)?)))
+    }
+    /// like `iter`, but the mobjects can be changed in place
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(MobjectId, &mut dyn mobjects::Mobject)) {
+        for id in &self.draw_order {
+            if let Some(m) = self.mobjects.get_mut(*id) {
+                f(*id, m);
+            }
+        }
+    }
+    /// ids of the mobjects in the scene, in the order they were added or moved to,
+    /// see `render_order` for the order they are drawn in
+    pub fn ids(&self) -> &[MobjectId] {
+        &self.draw_order
     }
 }
 
@@ -218,6 +550,82 @@ fn test_rectangle_image() {
     scene.save_png(&mut ctx, "rectangle.png");
 }
 
+#[test]
+fn test_background() {
+    use mobjects::gradient::GradientStop;
+    use mobjects::{DrawConfig, Rectangle, Transform};
+    let mut ctx = Context::default();
+    let mut scene = Scene::new();
+    let mut rectangle = Rectangle {
+        draw_config: DrawConfig::filled(Color::new(0xff, 0x00, 0x00, 0x80)),
+        ..Default::default()
+    };
+    rectangle.scale(4.0);
+    rectangle.move_this(nalgebra::Vector3::new(-2.0, -2.0, 0.0));
+    scene.add(Box::new(rectangle));
+
+    // frames keep straight alpha through to the video backend
+    scene.background = Some(Background::Transparent);
+    scene.render(&mut ctx);
+    let frame = ctx.image_bytes();
+    assert_eq!(frame[..4], [0, 0, 0, 0]);
+    let center = (540 * 1920 + 960) * 4;
+    assert_eq!(frame[center..center + 4], [0xff, 0x00, 0x00, 0x80]);
+
+    scene.background = Some(Background::Gradient(mobjects::gradient::Gradient::linear(
+        nalgebra::Point3::new(0.0, -4.5, 0.0),
+        nalgebra::Point3::new(0.0, 4.5, 0.0),
+        vec![
+            GradientStop::new(0.0, color::BLUE_E),
+            GradientStop::new(1.0, color::BLACK),
+        ],
+    )));
+    scene.save_png(&mut ctx, "background_gradient.png");
+    assert!(matches!(ctx.image_bytes(), Cow::Borrowed(_)));
+    assert_eq!(ctx.image_bytes()[3], 0xff);
+
+    let background = Background::from_image("background_gradient.png").unwrap();
+    assert!(Background::from_image("Cargo.toml").is_err());
+    scene.background = Some(background);
+    scene.save_png(&mut ctx, "background_image.png");
+}
+
+#[test]
+fn test_draw_order() {
+    use mobjects::{Mobject, Rectangle, Transform};
+    let mut ctx = Context::default();
+    let mut scene = Scene::new();
+    let a = scene.add(Box::new(Rectangle::default()));
+    let b = scene.add_typed(Rectangle::default());
+    let c = scene.add(Box::new(Rectangle::default()));
+    scene.get_mut(b).unwrap().move_this(nalgebra::Vector3::new(0.5, 0.5, 0.0));
+    scene.set_z_index(a, 1);
+    assert_eq!(scene.render_order(), vec![b.untyped(), c, a]);
+    scene.send_to_back(a);
+    assert_eq!(scene.z_index(a), 0);
+    assert_eq!(scene.render_order(), vec![a, b.untyped(), c]);
+    scene.bring_to_front(b);
+    assert_eq!(scene.render_order(), vec![a, c, b.untyped()]);
+
+    // only mobjects off the z = 0 plane move when depth sorting
+    scene.get_mut(c).unwrap().move_this(nalgebra::Vector3::new(0.0, 0.0, -1.0));
+    scene.depth_sort = true;
+    assert_eq!(scene.render_order(), vec![c, a, b.untyped()]);
+    scene.save_png(&mut ctx, "draw_order.png");
+
+    // lookups and iteration go through the scene
+    assert_eq!(scene.len(), 3);
+    assert_eq!(scene.downcast::<Rectangle>(a).map(|r| r.untyped()), Some(a));
+    let ids: Vec<MobjectId> = scene.iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, scene.ids());
+    let x = scene.get(b).unwrap().get_center().x;
+    scene.for_each_mut(|_, m| m.move_this(nalgebra::Vector3::new(1.0, 0.0, 0.0)));
+    assert_eq!(scene.get(b).unwrap().get_center().x, x + 1.0);
+    scene.remove(a);
+    assert!(!scene.contains(a));
+    assert_eq!(scene.iter().count(), 2);
+}
+
 #[test]
 fn write_frame() {
     use mobjects::Rectangle;
@@ -232,7 +640,7 @@ fn write_frame() {
         p3: nalgebra::Point3::new(0.0, 3.0, 0.0),
         ..Default::default()
     };
-    scene.add(Box::new(rectangle));
+    let rectangle = scene.add(Box::new(rectangle));
 
     use std::io::Write;
     use std::process::Command;
@@ -249,6 +657,7 @@ fn write_frame() {
         output_height: 1080,
         output_width: 1920,
         color_order: ColorOrder::Rgba,
+        alpha: false,
     };
     let mut video_backend_var = VideoBackend {
         backend_type: VideoBackendType::FFMPEG(FFMPEGBackend::new(
@@ -263,12 +672,9 @@ fn write_frame() {
         let translation =
             nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.01, 0.0, 0.0));
         let translation = nalgebra::Transform3::<GMFloat>::from_matrix_unchecked(translation);
-        scene.mobjects[0].borrow_mut().transform(translation);
-        ctx.clear_transparent();
-        for m in scene.mobjects.iter() {
-            m.borrow().draw(&mut ctx);
-        }
-        video_backend_var.write_frame(ctx.image_bytes());
+        scene.get_mut(rectangle).unwrap().transform(translation);
+        scene.render(&mut ctx);
+        video_backend_var.write_frame(&ctx.image_bytes());
         println!("takes {:?}", now.elapsed());
     }
 }
@@ -288,7 +694,7 @@ fn thread_frame_pass() {
         p3: nalgebra::Point3::new(0.0, 3.0, 0.0),
         ..Default::default()
     };
-    scene.add(Box::new(rectangle));
+    let rectangle = scene.add(Box::new(rectangle));
 
     use std::collections::VecDeque;
     use std::io::Write;
@@ -306,6 +712,7 @@ fn thread_frame_pass() {
         output_height: 1080,
         output_width: 1920,
         color_order: ColorOrder::Rgba,
+        alpha: false,
     };
 
     let mut video_backend_var = VideoBackend {
@@ -330,11 +737,8 @@ fn thread_frame_pass() {
         let translation =
             nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.01, 0.0, 0.0));
         let translation = nalgebra::Transform3::<GMFloat>::from_matrix_unchecked(translation);
-        scene.mobjects[0].borrow_mut().transform(translation);
-        ctx.clear_transparent();
-        for m in scene.mobjects.iter() {
-            m.borrow().draw(&mut ctx);
-        }
+        scene.get_mut(rectangle).unwrap().transform(translation);
+        scene.render(&mut ctx);
         let data_bytes = ctx.image_bytes().to_vec();
         {
             let mut queue_guard = queue.lock().unwrap();
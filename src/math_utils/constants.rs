@@ -1,3 +1,7 @@
+use nalgebra::Vector3;
+
+use crate::GMFloat;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "gmfloat_f16")]{
         pub const PI: f16 = std::f16::consts::PI;
@@ -9,3 +13,20 @@ cfg_if::cfg_if! {
         pub const PI: f32 = std::f32::consts::PI;
     }
 }
+
+pub const ORIGIN: Vector3<GMFloat> = Vector3::new(0.0, 0.0, 0.0);
+pub const UP: Vector3<GMFloat> = Vector3::new(0.0, 1.0, 0.0);
+pub const DOWN: Vector3<GMFloat> = Vector3::new(0.0, -1.0, 0.0);
+pub const LEFT: Vector3<GMFloat> = Vector3::new(-1.0, 0.0, 0.0);
+pub const RIGHT: Vector3<GMFloat> = Vector3::new(1.0, 0.0, 0.0);
+pub const OUT: Vector3<GMFloat> = Vector3::new(0.0, 0.0, 1.0);
+pub const IN: Vector3<GMFloat> = Vector3::new(0.0, 0.0, -1.0);
+pub const UL: Vector3<GMFloat> = Vector3::new(-1.0, 1.0, 0.0);
+pub const UR: Vector3<GMFloat> = Vector3::new(1.0, 1.0, 0.0);
+pub const DL: Vector3<GMFloat> = Vector3::new(-1.0, -1.0, 0.0);
+pub const DR: Vector3<GMFloat> = Vector3::new(1.0, -1.0, 0.0);
+
+/// default gap left by `next_to` between two mobjects
+pub const DEFAULT_MOBJECT_TO_MOBJECT_BUFFER: GMFloat = 0.25;
+/// default gap left by `to_edge` and `to_corner` to the frame border
+pub const DEFAULT_MOBJECT_TO_EDGE_BUFFER: GMFloat = 0.5;
@@ -136,8 +136,8 @@ fn test_bezier_curve() {
 }
 
 pub fn k_for_bezier_arc(theta: GMFloat) -> GMFloat {
-    // assume theta was less than pi/2
-    (4.0 / 3.0 * (1.0 - (theta as GMFloat).cos()) / (theta as GMFloat).sin()) as GMFloat
+    // assume theta was less than pi/2, same as 4/3 (1 - cos) / sin but 0 for an empty arc
+    4.0 / 3.0 * (theta / 2.0).tan()
 }
 
 #[inline]
@@ -0,0 +1,344 @@
+use nalgebra::{Point3, Vector2, Vector3};
+
+use crate::{
+    color,
+    math_utils::{
+        bezier_cubic,
+        constants::{DOWN, PI, RIGHT, UP},
+    },
+    theme::theme,
+    Color, Context, GMFloat, GMPoint, Scene,
+};
+
+use super::{text::Text, BoundingBox, Draw, DrawConfig, Mobject, PolyLine, Transform};
+
+pub const DEFAULT_BRACE_DEPTH: GMFloat = 0.25;
+pub const DEFAULT_ANNOTATION_BUFFER: GMFloat = 0.1;
+pub const DEFAULT_BRACE_LABEL_FONT_SIZE: GMFloat = 4.0;
+const CURVE_SAMPLES: usize = 8;
+
+fn annotation_draw_config(color: Color) -> DrawConfig {
+    DrawConfig::stroked(color, theme().thin_stroke_width)
+}
+
+/// append `samples` points of the quadratic bezier p0 p1 p2, p0 excluded
+fn push_quad(
+    points: &mut Vec<Vector2<GMFloat>>,
+    p0: Vector2<GMFloat>,
+    p1: Vector2<GMFloat>,
+    p2: Vector2<GMFloat>,
+) {
+    for i in 1..=CURVE_SAMPLES {
+        points.push(bezier_cubic(
+            p0,
+            p1,
+            p2,
+            i as GMFloat / CURVE_SAMPLES as GMFloat,
+        ));
+    }
+}
+
+/// the edge of `bounding_box` facing `direction`, only the dominant axis of direction counts
+fn facing_edge(bounding_box: &BoundingBox, direction: Vector3<GMFloat>) -> (GMPoint, GMPoint) {
+    let (min, max) = (bounding_box.min, bounding_box.max);
+    if direction.y.abs() >= direction.x.abs() {
+        let y = if direction.y > 0.0 { max.y } else { min.y };
+        (Point3::new(min.x, y, 0.0), Point3::new(max.x, y, 0.0))
+    } else {
+        let x = if direction.x > 0.0 { max.x } else { min.x };
+        (Point3::new(x, min.y, 0.0), Point3::new(x, max.y, 0.0))
+    }
+}
+
+fn bounding_box_of(mobject: &dyn Mobject) -> BoundingBox {
+    mobject
+        .bounding_box()
+        .unwrap_or(BoundingBox::new(Point3::origin(), Point3::origin()))
+}
+
+fn axis_direction(direction: Vector3<GMFloat>) -> Vector3<GMFloat> {
+    if direction.y.abs() >= direction.x.abs() {
+        Vector3::new(0.0, direction.y.signum(), 0.0)
+    } else {
+        Vector3::new(direction.x.signum(), 0.0, 0.0)
+    }
+}
+
+/// curly brace spanning a segment, its tip pointing away from the annotated edge
+#[derive(Clone)]
+pub struct Brace {
+    outline: PolyLine,
+    tip: GMPoint,
+    direction: Vector3<GMFloat>,
+}
+
+impl Brace {
+    /// brace from `start` to `end` that bulges towards `direction`
+    pub fn new(start: GMPoint, end: GMPoint, direction: Vector3<GMFloat>) -> Self {
+        let length = (end - start).norm();
+        let u = (end - start) / length.max(GMFloat::EPSILON);
+        let n = (direction - u * direction.dot(&u))
+            .try_normalize(GMFloat::EPSILON)
+            .unwrap_or(Vector3::new(-u.y, u.x, 0.0));
+
+        let h = DEFAULT_BRACE_DEPTH;
+        let q = (h / 2.0).min(length / 4.0);
+        let half = length / 2.0;
+        let v = Vector2::new;
+        let mut local = vec![v(0.0, 0.0)];
+        push_quad(&mut local, v(0.0, 0.0), v(0.0, h / 2.0), v(q, h / 2.0));
+        local.push(v(half - q, h / 2.0));
+        push_quad(
+            &mut local,
+            v(half - q, h / 2.0),
+            v(half, h / 2.0),
+            v(half, h),
+        );
+        push_quad(
+            &mut local,
+            v(half, h),
+            v(half, h / 2.0),
+            v(half + q, h / 2.0),
+        );
+        local.push(v(length - q, h / 2.0));
+        push_quad(
+            &mut local,
+            v(length - q, h / 2.0),
+            v(length, h / 2.0),
+            v(length, 0.0),
+        );
+
+        Self {
+            outline: PolyLine {
+                points: local
+                    .into_iter()
+                    .map(|p| start + u * p.x + n * p.y)
+                    .collect(),
+                draw_config: annotation_draw_config(theme().foreground),
+            },
+            tip: start + u * half + n * h,
+            direction: n,
+        }
+    }
+
+    /// brace along the side of `bounding_box` facing `direction`
+    pub fn for_bounding_box(bounding_box: &BoundingBox, direction: Vector3<GMFloat>) -> Self {
+        let direction = axis_direction(direction);
+        let (start, end) = facing_edge(bounding_box, direction);
+        let offset = direction * DEFAULT_ANNOTATION_BUFFER;
+        Self::new(start + offset, end + offset, direction)
+    }
+
+    pub fn for_mobject(mobject: &dyn Mobject, direction: Vector3<GMFloat>) -> Self {
+        Self::for_bounding_box(&bounding_box_of(mobject), direction)
+    }
+
+    pub fn tip(&self) -> GMPoint {
+        self.tip
+    }
+
+    /// unit vector from the annotated edge to the tip
+    pub fn direction(&self) -> Vector3<GMFloat> {
+        self.direction
+    }
+
+    /// move `text` next to the tip, on the far side of the brace
+    pub fn put_at_tip(&self, text: &mut Text) {
+        if let Some(b) = text.bounding_box() {
+            let target = self.tip + self.direction * DEFAULT_ANNOTATION_BUFFER;
+            text.move_this(target - b.critical_point(-axis_direction(self.direction)));
+        }
+    }
+}
+
+impl Transform for Brace {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.outline.transform(transform);
+        self.tip = transform * self.tip;
+        self.direction = (transform * self.direction)
+            .try_normalize(GMFloat::EPSILON)
+            .unwrap_or(self.direction);
+    }
+}
+
+impl Draw for Brace {
+    fn draw(&self, ctx: &mut Context) {
+        self.outline.draw(ctx);
+    }
+}
+
+impl Mobject for Brace {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.outline.bounding_box()
+    }
+
+    /// the outline followed by the tip
+    fn control_points(&self) -> Vec<GMPoint> {
+        let mut points = self.outline.points.clone();
+        points.push(self.tip);
+        points
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        if let Some((tip, outline)) = points.split_last() {
+            self.outline.points = outline.to_vec();
+            self.tip = *tip;
+        }
+    }
+}
+
+/// a brace with a text label placed beyond its tip
+#[derive(Clone)]
+pub struct BraceLabel {
+    pub brace: Brace,
+    pub label: Text,
+}
+
+impl BraceLabel {
+    pub fn new(bounding_box: &BoundingBox, text: &str, direction: Vector3<GMFloat>) -> Self {
+        let brace = Brace::for_bounding_box(bounding_box, direction);
+        let mut label = Text::new(
+            text.to_owned(),
+            Point3::origin(),
+            DEFAULT_BRACE_LABEL_FONT_SIZE,
+            DrawConfig::text_default(),
+        );
+        brace.put_at_tip(&mut label);
+        Self { brace, label }
+    }
+
+    pub fn for_mobject(mobject: &dyn Mobject, text: &str, direction: Vector3<GMFloat>) -> Self {
+        Self::new(&bounding_box_of(mobject), text, direction)
+    }
+}
+
+impl Transform for BraceLabel {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.brace.transform(transform);
+        self.label.transform(transform);
+    }
+}
+
+impl Draw for BraceLabel {
+    fn draw(&self, ctx: &mut Context) {
+        self.brace.draw(ctx);
+        self.label.draw(ctx);
+    }
+}
+
+impl Mobject for BraceLabel {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all([self.brace.bounding_box(), self.label.bounding_box()])
+    }
+
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        vec![&self.brace, &self.label]
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        vec![&mut self.brace, &mut self.label]
+    }
+}
+
+/// rectangle outline around a bounding box, with padding and optionally rounded corners
+#[derive(Clone)]
+pub struct SurroundingRectangle {
+    outline: PolyLine,
+}
+
+impl SurroundingRectangle {
+    pub fn new(bounding_box: &BoundingBox, buff: GMFloat, corner_radius: GMFloat) -> Self {
+        let b = bounding_box.grow(buff);
+        let radius = corner_radius
+            .min(b.width() / 2.0)
+            .min(b.height() / 2.0)
+            .max(0.0);
+        let mut points = vec![];
+        // one quarter circle per corner, counter-clockwise from the lower right
+        let corners = [
+            (b.critical_point(DOWN + RIGHT), -PI / 2.0),
+            (b.critical_point(UP + RIGHT), 0.0),
+            (b.critical_point(UP - RIGHT), PI / 2.0),
+            (b.critical_point(DOWN - RIGHT), PI),
+        ];
+        for (corner, start_angle) in corners {
+            // center of the corner arc, `radius` inside the corner on both axes
+            let center = corner
+                - Vector3::new(
+                    start_angle.cos() + (start_angle + PI / 2.0).cos(),
+                    start_angle.sin() + (start_angle + PI / 2.0).sin(),
+                    0.0,
+                ) * radius;
+            for i in 0..=CURVE_SAMPLES {
+                let angle = start_angle + PI / 2.0 * i as GMFloat / CURVE_SAMPLES as GMFloat;
+                points.push(center + Vector3::new(angle.cos(), angle.sin(), 0.0) * radius);
+            }
+        }
+        points.push(points[0]);
+        Self {
+            outline: PolyLine {
+                points,
+                draw_config: annotation_draw_config(color::YELLOW),
+            },
+        }
+    }
+
+    pub fn for_mobject(mobject: &dyn Mobject, buff: GMFloat, corner_radius: GMFloat) -> Self {
+        Self::new(&bounding_box_of(mobject), buff, corner_radius)
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.outline.draw_config.stroke_color = color;
+    }
+}
+
+impl Transform for SurroundingRectangle {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.outline.transform(transform);
+    }
+}
+
+impl Draw for SurroundingRectangle {
+    fn draw(&self, ctx: &mut Context) {
+        self.outline.draw(ctx);
+    }
+}
+
+impl Mobject for SurroundingRectangle {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.outline.bounding_box()
+    }
+
+    fn control_points(&self) -> Vec<GMPoint> {
+        self.outline.control_points()
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        self.outline.set_control_points(points);
+    }
+}
+
+#[test]
+fn test_annotations() {
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let text = Text::new(
+        "annotated".to_owned(),
+        Point3::new(-2.0, 0.0, 0.0),
+        8.0,
+        DrawConfig::filled(Color::new(0xff, 0xff, 0xff, 0xff)),
+    );
+    let brace = Brace::for_mobject(&text, UP);
+    assert!(brace.tip().y > text.get_top().y);
+    scene.add(Box::new(brace));
+    scene.add(Box::new(BraceLabel::for_mobject(&text, "width", DOWN)));
+    scene.add(Box::new(BraceLabel::for_mobject(&text, "h", RIGHT)));
+    scene.add(Box::new(SurroundingRectangle::for_mobject(&text, 0.2, 0.2)));
+    scene.add(Box::new(text));
+    scene.add(Box::new(Brace::new(
+        Point3::new(3.0, -2.0, 0.0),
+        Point3::new(6.0, 1.0, 0.0),
+        Vector3::new(1.0, -1.0, 0.0),
+    )));
+    scene.save_png(&mut ctx, "annotations.png");
+}
@@ -0,0 +1,238 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use super::Mobject;
+
+/// handle to a mobject stored in a [`MobjectArena`]
+///
+/// `T` is the type the handle resolves to, `dyn Mobject` for untyped handles.
+/// a handle stays valid until its mobject is removed, after that it never
+/// resolves again, even when the slot is reused.
+pub struct MobjectId<T: ?Sized = dyn Mobject> {
+    index: usize,
+    generation: u32,
+    _marker: PhantomData<fn() -> Box<T>>,
+}
+
+impl<T: ?Sized> MobjectId<T> {
+    fn new(index: usize, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            _marker: PhantomData,
+        }
+    }
+
+    /// the same handle without the type
+    pub fn untyped(self) -> MobjectId {
+        MobjectId::new(self.index, self.generation)
+    }
+}
+
+impl<T: ?Sized> Clone for MobjectId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for MobjectId<T> {}
+
+impl<T: ?Sized> PartialEq for MobjectId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T: ?Sized> Eq for MobjectId<T> {}
+
+impl<T: ?Sized> Hash for MobjectId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for MobjectId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MobjectId({}v{})", self.index, self.generation)
+    }
+}
+
+/// types a stored mobject can be viewed as, `dyn Mobject` or a concrete mobject
+pub trait MobjectKind {
+    fn from_dyn<'a>(mobject: &'a (dyn Mobject + 'static)) -> Option<&'a Self>;
+    fn from_dyn_mut<'a>(mobject: &'a mut (dyn Mobject + 'static)) -> Option<&'a mut Self>;
+}
+
+impl MobjectKind for dyn Mobject {
+    fn from_dyn<'a>(mobject: &'a (dyn Mobject + 'static)) -> Option<&'a Self> {
+        Some(mobject)
+    }
+    fn from_dyn_mut<'a>(mobject: &'a mut (dyn Mobject + 'static)) -> Option<&'a mut Self> {
+        Some(mobject)
+    }
+}
+
+impl<T: Mobject + 'static> MobjectKind for T {
+    fn from_dyn<'a>(mobject: &'a (dyn Mobject + 'static)) -> Option<&'a Self> {
+        mobject.as_any().downcast_ref()
+    }
+    fn from_dyn_mut<'a>(mobject: &'a mut (dyn Mobject + 'static)) -> Option<&'a mut Self> {
+        mobject.as_any_mut().downcast_mut()
+    }
+}
+
+struct Slot {
+    generation: u32,
+    mobject: Option<Box<dyn Mobject>>,
+}
+
+/// owns mobjects and hands out [`MobjectId`]s for them, freed slots are reused
+#[derive(Default)]
+pub struct MobjectArena {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+impl MobjectArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<T: Mobject + 'static>(&mut self, mobject: T) -> MobjectId<T> {
+        let id = self.insert_boxed(Box::new(mobject));
+        MobjectId::new(id.index, id.generation)
+    }
+
+    pub fn insert_boxed(&mut self, mobject: Box<dyn Mobject>) -> MobjectId {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.mobject = Some(mobject);
+                MobjectId::new(index, slot.generation)
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    mobject: Some(mobject),
+                });
+                MobjectId::new(self.slots.len() - 1, 0)
+            }
+        }
+    }
+
+    /// take the mobject out, every handle to it is invalid afterwards
+    pub fn remove<T: ?Sized>(&mut self, id: MobjectId<T>) -> Option<Box<dyn Mobject>> {
+        let slot = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let mobject = slot.mobject.take()?;
+        slot.generation += 1;
+        self.free.push(id.index);
+        Some(mobject)
+    }
+
+    /// swap the stored mobject for `mobject`, the id stays valid, returns the old one
+    pub fn replace_boxed<T: ?Sized>(
+        &mut self,
+        id: MobjectId<T>,
+        mobject: Box<dyn Mobject>,
+    ) -> Option<Box<dyn Mobject>> {
+        let slot = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.mobject
+            .as_mut()
+            .map(|old| std::mem::replace(old, mobject))
+    }
+
+    pub fn contains<T: ?Sized>(&self, id: MobjectId<T>) -> bool {
+        self.get_dyn(id.untyped()).is_some()
+    }
+
+    /// None when the mobject was removed or isn't a `T`
+    pub fn get<T: MobjectKind + ?Sized>(&self, id: MobjectId<T>) -> Option<&T> {
+        T::from_dyn(self.get_dyn(id.untyped())?)
+    }
+
+    pub fn get_mut<T: MobjectKind + ?Sized>(&mut self, id: MobjectId<T>) -> Option<&mut T> {
+        let slot = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        T::from_dyn_mut(slot.mobject.as_deref_mut()?)
+    }
+
+    /// typed handle to the same mobject, if it is a `T`
+    pub fn downcast<T: Mobject + 'static>(&self, id: MobjectId) -> Option<MobjectId<T>> {
+        self.get_dyn(id)?
+            .as_any()
+            .is::<T>()
+            .then(|| MobjectId::new(id.index, id.generation))
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.mobject.take().is_some() {
+                slot.generation += 1;
+                self.free.push(index);
+            }
+        }
+    }
+
+    fn get_dyn(&self, id: MobjectId) -> Option<&(dyn Mobject + 'static)> {
+        let slot = self.slots.get(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.mobject.as_deref()
+    }
+}
+
+#[test]
+fn test_arena() {
+    use super::{Rectangle, SimpleLine, Transform};
+
+    let mut arena = MobjectArena::new();
+    let rectangle = arena.insert(Rectangle::default());
+    let line = arena.insert_boxed(Box::new(SimpleLine::default()));
+    assert_eq!(arena.len(), 2);
+    assert!(arena.get(rectangle).is_some());
+    assert!(arena.downcast::<Rectangle>(line).is_none());
+    let line = arena.downcast::<SimpleLine>(line).unwrap();
+    arena.get_mut(line).unwrap().p1.x = 2.0;
+    assert_eq!(arena.get(line).unwrap().p1.x, 2.0);
+
+    assert!(arena.remove(rectangle).is_some());
+    assert!(arena.remove(rectangle).is_none());
+    assert!(!arena.contains(rectangle));
+    // the freed slot is reused but the stale handle stays dead
+    let polyline = arena.insert(super::PolyLine::default());
+    assert_eq!(polyline.untyped().index, rectangle.untyped().index);
+    assert!(arena.get(rectangle).is_none());
+    assert!(arena.get(polyline.untyped()).is_some());
+    arena.clear();
+    assert!(arena.is_empty());
+
+    let mut ctx = crate::Context::default();
+    let mut scene = crate::Scene::new();
+    let back = scene.add_typed(Rectangle::default());
+    let front = scene.add(Box::new(SimpleLine::default()));
+    scene.bring_to_front(back);
+    assert_eq!(scene.ids(), &[front, back.untyped()]);
+    scene.get_mut(back).unwrap().scale(2.0);
+    assert!(scene.remove(front).is_some());
+    assert_eq!(scene.ids().len(), 1);
+    scene.save_png(&mut ctx, "arena.png");
+}
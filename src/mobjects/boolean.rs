@@ -0,0 +1,431 @@
+use std::collections::{HashMap, HashSet};
+
+use nalgebra::{Point2, Point3, Vector2};
+use tiny_skia::FillRule;
+
+use crate::{Context, GMFloat};
+
+use super::path::{control_points, flatten, polyline_elements, to_pixel_path, Path};
+use super::{BoundingBox, Draw, DrawConfig, Mobject, PathElement, Transform};
+
+/// shorter pieces are dropped and closer points are the same point
+const EPSILON: GMFloat = 1e-4;
+/// distance from an edge at which the inside of both shapes is probed
+const PROBE: GMFloat = 1e-3;
+
+type Segment = (Point2<GMFloat>, Point2<GMFloat>);
+
+/// how the areas of shapes combine, e.g. into venn diagrams or cut-out shapes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// covered by any shape
+    Union,
+    /// covered by every shape
+    Intersection,
+    /// covered by the first shape and none of the others
+    Difference,
+    /// covered by an odd number of shapes
+    Exclusion,
+}
+
+impl BooleanOp {
+    fn keep(&self, a: bool, b: bool) -> bool {
+        match self {
+            BooleanOp::Union => a || b,
+            BooleanOp::Intersection => a && b,
+            BooleanOp::Difference => a && !b,
+            BooleanOp::Exclusion => a != b,
+        }
+    }
+
+    /// the outline of the combined fill areas, curves are flattened and open subpaths
+    /// count as closed
+    pub fn elements(
+        &self,
+        a: &[PathElement],
+        b: &[PathElement],
+        rules: [FillRule; 2],
+    ) -> Vec<PathElement> {
+        let (a, b) = (segments(a), segments(b));
+        let mut pieces = split(a.iter().chain(&b).copied().collect());
+        dedup(&mut pieces);
+        let inside =
+            |p: Point2<GMFloat>| self.keep(is_inside(&a, rules[0], p), is_inside(&b, rules[1], p));
+        let boundary = pieces
+            .into_iter()
+            .filter_map(|(p, q)| {
+                let d = (q - p).normalize();
+                let left = Vector2::new(-d.y, d.x) * PROBE;
+                let mid = p + (q - p) / 2.0;
+                // the combined area ends up to the left of every edge
+                match (inside(mid + left), inside(mid - left)) {
+                    (true, false) => Some((p, q)),
+                    (false, true) => Some((q, p)),
+                    _ => None,
+                }
+            })
+            .collect();
+        link(boundary)
+            .iter()
+            .flat_map(|points| polyline_elements(points, true))
+            .collect()
+    }
+
+    /// combine the areas of `mobjects` in order, the result takes the style of the
+    /// first one and can be filled, stroked and animated like any [`Path`]; see
+    /// [`Union`] and the others for a shape that follows its inputs around
+    pub fn apply(&self, mobjects: &[&dyn Mobject]) -> Path {
+        Path {
+            elements: self.combine(mobjects),
+            draw_config: first_style(mobjects.first().copied()),
+        }
+    }
+
+    /// outline of the areas of `mobjects` combined in order
+    fn combine(&self, mobjects: &[&dyn Mobject]) -> Vec<PathElement> {
+        let rule = |m: &dyn Mobject| m.draw_config().map(|d| d.fill_rule).unwrap_or_default();
+        let Some((first, rest)) = mobjects.split_first() else {
+            return vec![];
+        };
+        let mut elements = first.path_elements();
+        let mut first_rule = rule(*first);
+        for m in rest {
+            elements = self.elements(&elements, &m.path_elements(), [first_rule, rule(*m)]);
+            // the edges of the result never cross
+            first_rule = FillRule::Winding;
+        }
+        elements
+    }
+}
+
+fn first_style(first: Option<&dyn Mobject>) -> DrawConfig {
+    first
+        .and_then(|m| m.draw_config().cloned())
+        .unwrap_or_default()
+}
+
+/// a mobject holding its inputs, so the combined outline is worked out again whenever
+/// it is drawn, after the inputs moved or were animated
+macro_rules! boolean_mobject {
+    ($(#[$doc:meta])* $name:ident, $op:expr) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            pub mobjects: Vec<Box<dyn Mobject>>,
+            pub draw_config: DrawConfig,
+        }
+
+        impl $name {
+            /// styled like the first mobject
+            pub fn new(mobjects: Vec<Box<dyn Mobject>>) -> Self {
+                Self {
+                    draw_config: first_style(mobjects.first().map(|m| m.as_ref())),
+                    mobjects,
+                }
+            }
+
+            /// the outline as it is now, as a standalone path
+            pub fn to_path(&self) -> Path {
+                Path {
+                    elements: self.path_elements(),
+                    draw_config: self.draw_config.clone(),
+                }
+            }
+        }
+
+        impl Transform for $name {
+            fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+                for m in &mut self.mobjects {
+                    m.transform(transform);
+                }
+                self.draw_config.transform_gradients(transform);
+            }
+        }
+
+        impl Draw for $name {
+            fn draw(&self, ctx: &mut Context) {
+                if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+                    self.draw_config.render_path(ctx, &path);
+                }
+            }
+        }
+
+        impl Mobject for $name {
+            fn bounding_box(&self) -> Option<BoundingBox> {
+                BoundingBox::from_points(control_points(&self.path_elements()))
+            }
+
+            /// the inputs, animating them reshapes the result
+            fn submobjects(&self) -> Vec<&dyn Mobject> {
+                self.mobjects.iter().map(|m| m.as_ref()).collect()
+            }
+
+            fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+                self.mobjects
+                    .iter_mut()
+                    .map(|m| &mut **m as &mut dyn Mobject)
+                    .collect()
+            }
+
+            fn path_elements(&self) -> Vec<PathElement> {
+                let inputs: Vec<&dyn Mobject> = self.mobjects.iter().map(|m| m.as_ref()).collect();
+                $op.combine(&inputs)
+            }
+
+            fn draw_config(&self) -> Option<&DrawConfig> {
+                Some(&self.draw_config)
+            }
+
+            fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+                Some(&mut self.draw_config)
+            }
+        }
+    };
+}
+
+boolean_mobject!(
+    /// the area covered by any of `mobjects`
+    Union,
+    BooleanOp::Union
+);
+boolean_mobject!(
+    /// the area covered by every one of `mobjects`
+    Intersection,
+    BooleanOp::Intersection
+);
+boolean_mobject!(
+    /// the area of the first of `mobjects` that none of the others cover
+    Difference,
+    BooleanOp::Difference
+);
+boolean_mobject!(
+    /// the area covered by an odd number of `mobjects`
+    Exclusion,
+    BooleanOp::Exclusion
+);
+
+/// the edges of every subpath, each closed back to its start
+fn segments(elements: &[PathElement]) -> Vec<Segment> {
+    flatten(elements)
+        .into_iter()
+        .flat_map(|subpath| {
+            let mut points: Vec<Point2<GMFloat>> = subpath.iter().map(|p| p.xy()).collect();
+            if let (Some(first), Some(last)) = (points.first(), points.last()) {
+                if (first - last).norm() > EPSILON {
+                    points.push(*first);
+                }
+            }
+            points
+                .windows(2)
+                .map(|w| (w[0], w[1]))
+                .filter(|(p, q)| (q - p).norm() > EPSILON)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn cross(a: Vector2<GMFloat>, b: Vector2<GMFloat>) -> GMFloat {
+    a.x * b.y - a.y * b.x
+}
+
+/// winding number test against all `segments`
+fn is_inside(segments: &[Segment], rule: FillRule, p: Point2<GMFloat>) -> bool {
+    let mut winding = 0;
+    for (a, b) in segments {
+        let side = cross(b - a, p - a);
+        if a.y <= p.y && b.y > p.y && side > 0.0 {
+            winding += 1;
+        } else if a.y > p.y && b.y <= p.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    match rule {
+        FillRule::Winding => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// cut the segments wherever they cross or touch, so no two overlap but at their ends
+fn split(segments: Vec<Segment>) -> Vec<Segment> {
+    let mut cuts: Vec<Vec<(GMFloat, Point2<GMFloat>)>> = vec![vec![]; segments.len()];
+    for i in 0..segments.len() {
+        for j in i + 1..segments.len() {
+            let ((a, b), (c, d)) = (segments[i], segments[j]);
+            let (r, s) = (b - a, d - c);
+            let denominator = cross(r, s);
+            if denominator.abs() > EPSILON * r.norm() * s.norm() {
+                let t = cross(c - a, s) / denominator;
+                let u = cross(c - a, r) / denominator;
+                let (tol_t, tol_u) = (EPSILON / r.norm(), EPSILON / s.norm());
+                if t < -tol_t || t > 1.0 + tol_t || u < -tol_u || u > 1.0 + tol_u {
+                    continue;
+                }
+                // a crossing at an end point is that very point, so pieces link up
+                let p = if t < tol_t {
+                    a
+                } else if t > 1.0 - tol_t {
+                    b
+                } else if u < tol_u {
+                    c
+                } else if u > 1.0 - tol_u {
+                    d
+                } else {
+                    a + r * t
+                };
+                cuts[i].push((t, p));
+                cuts[j].push((u, p));
+            } else if cross(r, c - a).abs() < EPSILON * r.norm() {
+                // collinear, each is cut at the ends of the other
+                for (k, (p, v), ends) in [(i, (a, r), [c, d]), (j, (c, s), [a, b])] {
+                    for e in ends {
+                        cuts[k].push(((e - p).dot(&v) / v.norm_squared(), e));
+                    }
+                }
+            }
+        }
+    }
+    segments
+        .into_iter()
+        .zip(cuts)
+        .flat_map(|((a, b), mut cuts)| {
+            cuts.retain(|(t, _)| *t > 0.0 && *t < 1.0);
+            cuts.sort_by(|x, y| x.0.total_cmp(&y.0));
+            let points: Vec<Point2<GMFloat>> = std::iter::once(a)
+                .chain(cuts.into_iter().map(|(_, p)| p))
+                .chain(std::iter::once(b))
+                .collect();
+            points
+                .windows(2)
+                .map(|w| (w[0], w[1]))
+                .filter(|(p, q)| (q - p).norm() > EPSILON)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn key(p: Point2<GMFloat>) -> (i64, i64) {
+    (
+        (p.x / EPSILON).round() as i64,
+        (p.y / EPSILON).round() as i64,
+    )
+}
+
+/// drop pieces shared by both shapes, one copy is enough to tell the sides apart
+fn dedup(pieces: &mut Vec<Segment>) {
+    let mut seen = HashSet::new();
+    pieces.retain(|(p, q)| {
+        let (kp, kq) = (key(*p), key(*q));
+        seen.insert(if kp < kq { (kp, kq) } else { (kq, kp) })
+    });
+}
+
+/// chain directed edges into closed loops
+fn link(edges: Vec<Segment>) -> Vec<Vec<Point3<GMFloat>>> {
+    let mut starting_at: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, (p, _)) in edges.iter().enumerate() {
+        starting_at.entry(key(*p)).or_default().push(i);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut loops = vec![];
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let mut points = vec![edges[start].0];
+        let mut end = edges[start].1;
+        while key(end) != key(points[0]) {
+            let next = starting_at
+                .get(&key(end))
+                .and_then(|candidates| candidates.iter().find(|i| !used[**i]));
+            let Some(&next) = next else {
+                break;
+            };
+            used[next] = true;
+            points.push(end);
+            end = edges[next].1;
+        }
+        if points.len() > 2 {
+            loops.push(points.iter().map(|p| Point3::new(p.x, p.y, 0.0)).collect());
+        }
+    }
+    loops
+}
+
+#[test]
+fn test_boolean_ops() {
+    use super::{Arc, BoundingBox, Rectangle, Transform};
+    use crate::{color, math_utils::constants::PI, Context, Scene};
+    use nalgebra::Vector3;
+
+    let square = |x: GMFloat, y: GMFloat| {
+        let mut square = Rectangle::default();
+        square.scale(2.0);
+        square.move_this(Vector3::new(x, y, 0.0));
+        square
+    };
+    let area = |path: &Path| -> GMFloat {
+        flatten(&path.elements)
+            .iter()
+            .flat_map(|points| {
+                points
+                    .windows(2)
+                    .map(|w| cross(w[0].coords.xy(), w[1].coords.xy()))
+            })
+            .sum::<GMFloat>()
+            / 2.0
+    };
+    let (a, b) = (square(0.0, 0.0), square(1.0, 1.0));
+    let ops = [
+        (BooleanOp::Union, 7.0),
+        (BooleanOp::Intersection, 1.0),
+        (BooleanOp::Difference, 3.0),
+        (BooleanOp::Exclusion, 6.0),
+    ];
+    for (op, expected) in ops {
+        let path = op.apply(&[&a, &b]);
+        assert!((area(&path) - expected).abs() < 1e-3, "{:?}", op);
+    }
+    // squares sharing a side merge into one outline
+    let merged = BooleanOp::Union.apply(&[&a, &square(2.0, 0.0)]);
+    let b = merged.bounding_box().unwrap();
+    assert_eq!((b.width(), b.height()), (4.0, 2.0));
+    assert_eq!(flatten(&merged.elements).len(), 1);
+    // a hole cut out of the middle keeps its own outline
+    let mut big = square(-1.0, -1.0);
+    big.scale(3.0);
+    let ring = BooleanOp::Difference.apply(&[&big, &a]);
+    assert!((area(&ring) - 32.0).abs() < 1e-3);
+    assert_eq!(flatten(&ring.elements).len(), 2);
+
+    // the mobject versions follow their inputs around
+    let mut union = Union::new(vec![Box::new(a.clone()), Box::new(square(1.0, 1.0))]);
+    assert!((area(&union.to_path()) - 7.0).abs() < 1e-3);
+    union.mobjects[1].move_this(Vector3::new(5.0, 0.0, 0.0));
+    assert!((area(&union.to_path()) - 8.0).abs() < 1e-3);
+    union.move_this(Vector3::new(0.0, -1.0, 0.0));
+    let b = union.bounding_box().unwrap();
+    assert_eq!(b.min, Point3::new(0.0, -1.0, 0.0));
+    let difference = Difference::new(vec![Box::new(big.clone()), Box::new(a.clone())]);
+    assert_eq!(flatten(&difference.path_elements()).len(), 2);
+
+    // venn diagram
+    let circle = |x: GMFloat, c: color::Color| {
+        let mut circle = Arc::new(Point3::new(x, 1.0, 0.0), 0.0, 2.0 * PI, 2.0);
+        circle.draw_config = DrawConfig::filled(c);
+        circle
+    };
+    let (left, right) = (circle(-1.0, color::BLUE), circle(1.0, color::RED));
+    let mut middle = Intersection::new(vec![Box::new(left.clone()), Box::new(right.clone())]);
+    middle.draw_config = DrawConfig::filled(color::PURPLE);
+    let mut cutout = BooleanOp::Exclusion.apply(&[&left, &right]);
+    cutout.scale(0.5);
+    cutout.move_this(Vector3::new(0.0, -3.0, 0.0));
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    scene.add(Box::new(left));
+    scene.add(Box::new(right));
+    scene.add(Box::new(middle));
+    scene.add(Box::new(cutout));
+    scene.save_png(&mut ctx, "boolean_ops.png");
+}
@@ -0,0 +1,591 @@
+use nalgebra::{Point3, Vector3};
+
+use crate::{
+    data::{DataError, DataTable},
+    math_utils::constants::PI,
+    theme::theme,
+    Color, Context, GMFloat, GMPoint, Scene,
+};
+
+use super::{
+    polygon::Polygon, text::Text, BoundingBox, Draw, DrawConfig, Mobject, PolyLine, SimpleLine,
+    Transform,
+};
+
+pub const DEFAULT_CHART_SIZE: (GMFloat, GMFloat) = (8.0, 4.5);
+pub const DEFAULT_LABEL_FONT_SIZE: GMFloat = 4.0;
+const LABEL_BUFFER: GMFloat = 0.25;
+const PIE_SEGMENTS: usize = 64;
+
+pub(crate) fn chart_color(index: usize) -> Color {
+    theme().palette_color(index)
+}
+
+fn label_draw_config() -> DrawConfig {
+    DrawConfig::filled(theme().foreground)
+}
+
+fn axis_draw_config() -> DrawConfig {
+    let theme = theme();
+    DrawConfig::stroked(theme.foreground, theme.thin_stroke_width)
+}
+
+/// build a label whose anchor point (a fraction of its extent, e.g. (0.5, 1.0) for top center)
+/// sits on `anchor`
+fn build_label(text: &str, anchor: GMPoint, alignment: (GMFloat, GMFloat)) -> Text {
+    let mut label = Text::new(
+        text.to_owned(),
+        Point3::origin(),
+        DEFAULT_LABEL_FONT_SIZE,
+        label_draw_config(),
+    );
+    if let Some(b) = label.bounding_box() {
+        // move the glyphs rather than `position`, so later transforms apply to the offset too
+        label.move_this(Vector3::new(
+            anchor.x - b.min.x - b.width() * alignment.0,
+            anchor.y - b.min.y - b.height() * alignment.1,
+            0.0,
+        ));
+    }
+    label
+}
+
+/// values of a chart that can be morphed into another data series
+pub trait DataChart: Mobject {
+    fn values(&self) -> &[GMFloat];
+    /// show `from` moved `t` of the way to `to`, missing entries count as zero
+    fn interpolate_values(&mut self, from: &[GMFloat], to: &[GMFloat], t: GMFloat);
+    fn set_values(&mut self, values: &[GMFloat]) {
+        self.interpolate_values(values, values, 1.0);
+    }
+}
+
+pub fn lerp_values(from: &[GMFloat], to: &[GMFloat], t: GMFloat) -> Vec<GMFloat> {
+    (0..from.len().max(to.len()))
+        .map(|i| {
+            let a = from.get(i).copied().unwrap_or(0.0);
+            let b = to.get(i).copied().unwrap_or(0.0);
+            a * (1.0 - t) + b * t
+        })
+        .collect()
+}
+
+/// x axis along the bottom and y axis on the left of a `size` plotting area centered at origin
+fn build_axes(size: (GMFloat, GMFloat)) -> Vec<SimpleLine> {
+    let (half_w, half_h) = (size.0 / 2.0, size.1 / 2.0);
+    vec![
+        SimpleLine {
+            p0: Point3::new(-half_w, -half_h, 0.0),
+            p1: Point3::new(half_w, -half_h, 0.0),
+            draw_config: axis_draw_config(),
+        },
+        SimpleLine {
+            p0: Point3::new(-half_w, -half_h, 0.0),
+            p1: Point3::new(-half_w, half_h, 0.0),
+            draw_config: axis_draw_config(),
+        },
+    ]
+}
+
+/// center x of the `index`th of `count` evenly spaced slots along the x axis
+fn slot_x(size: (GMFloat, GMFloat), index: usize, count: usize) -> GMFloat {
+    let slot_width = size.0 / count.max(1) as GMFloat;
+    -size.0 / 2.0 + slot_width * (index as GMFloat + 0.5)
+}
+
+/// one label under each of `slots` slots, moved by `transform` like the rest of the chart
+fn build_x_labels(
+    size: (GMFloat, GMFloat),
+    labels: &[String],
+    slots: usize,
+    transform: nalgebra::Transform3<GMFloat>,
+) -> Vec<Text> {
+    labels
+        .iter()
+        .take(slots)
+        .enumerate()
+        .map(|(i, l)| {
+            let anchor = Point3::new(slot_x(size, i, slots), -size.1 / 2.0 - LABEL_BUFFER, 0.0);
+            let mut label = build_label(l, anchor, (0.5, 1.0));
+            label.transform(transform);
+            label
+        })
+        .collect()
+}
+
+/// labels and values of a chart taken from two columns of a table
+fn read_series(
+    table: &DataTable,
+    label_column: &str,
+    value_column: &str,
+) -> Result<(Vec<GMFloat>, Vec<String>), DataError> {
+    Ok((table.numbers(value_column)?, table.texts(label_column)?))
+}
+
+fn max_value(values: &[GMFloat]) -> GMFloat {
+    values.iter().copied().fold(0.0, GMFloat::max)
+}
+
+/// `y_max` when set, else the largest value at either end of a transition so the
+/// scale holds still while it runs
+fn fit_y_max(y_max: Option<GMFloat>, from: &[GMFloat], to: &[GMFloat]) -> GMFloat {
+    y_max
+        .unwrap_or_else(|| max_value(from).max(max_value(to)))
+        .max(GMFloat::EPSILON)
+}
+
+#[derive(Clone)]
+pub struct BarChart {
+    pub size: (GMFloat, GMFloat),
+    /// value drawn at the top of the plotting area, None fits the largest value
+    pub y_max: Option<GMFloat>,
+    /// fraction of each slot covered by its bar
+    pub bar_width: GMFloat,
+    labels: Vec<String>,
+    values: Vec<GMFloat>,
+    axes: Vec<SimpleLine>,
+    bars: Vec<Polygon>,
+    label_texts: Vec<Text>,
+    /// slot count `label_texts` were laid out for
+    label_slots: usize,
+    accumulated_transform: nalgebra::Transform3<GMFloat>,
+}
+
+impl BarChart {
+    pub fn from_data(
+        table: &DataTable,
+        label_column: &str,
+        value_column: &str,
+    ) -> Result<Self, DataError> {
+        let (values, labels) = read_series(table, label_column, value_column)?;
+        Ok(Self::new(values, labels))
+    }
+
+    pub fn new(values: Vec<GMFloat>, labels: Vec<String>) -> Self {
+        let size = DEFAULT_CHART_SIZE;
+        let mut chart = Self {
+            y_max: None,
+            axes: build_axes(size),
+            label_texts: vec![],
+            labels,
+            size,
+            bar_width: 0.6,
+            values: vec![],
+            bars: vec![],
+            label_slots: 0,
+            accumulated_transform: nalgebra::Transform3::identity(),
+        };
+        chart.set_values(&values);
+        chart
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// relabel the slots, one slot is added per label beyond the values
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        self.labels = labels;
+        // forces the labels to be rebuilt
+        self.label_slots = 0;
+        let values = self.values.clone();
+        self.set_values(&values);
+    }
+
+    fn slots(&self) -> usize {
+        self.values.len().max(self.labels.len())
+    }
+
+    fn build_bars(&self, values: &[GMFloat], y_max: GMFloat) -> Vec<Polygon> {
+        let slots = self.slots();
+        let half_bar = self.size.0 / slots.max(1) as GMFloat * self.bar_width / 2.0;
+        let bottom = -self.size.1 / 2.0;
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let x = slot_x(self.size, i, slots);
+                let top = bottom + v / y_max * self.size.1;
+                let mut bar = Polygon::new(vec![
+                    Point3::new(x - half_bar, bottom, 0.0),
+                    Point3::new(x + half_bar, bottom, 0.0),
+                    Point3::new(x + half_bar, top, 0.0),
+                    Point3::new(x - half_bar, top, 0.0),
+                ]);
+                bar.draw_config.fill_color = chart_color(i);
+                bar.transform(self.accumulated_transform);
+                bar
+            })
+            .collect()
+    }
+}
+
+impl DataChart for BarChart {
+    fn values(&self) -> &[GMFloat] {
+        &self.values
+    }
+    fn interpolate_values(&mut self, from: &[GMFloat], to: &[GMFloat], t: GMFloat) {
+        self.values = lerp_values(from, to, t);
+        self.bars = self.build_bars(&self.values, fit_y_max(self.y_max, from, to));
+        let slots = self.slots();
+        if self.label_slots != slots {
+            self.label_slots = slots;
+            self.label_texts =
+                build_x_labels(self.size, &self.labels, slots, self.accumulated_transform);
+        }
+    }
+}
+
+impl Transform for BarChart {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.axes.iter_mut().for_each(|m| m.transform(transform));
+        self.bars.iter_mut().for_each(|m| m.transform(transform));
+        self.label_texts
+            .iter_mut()
+            .for_each(|m| m.transform(transform));
+        self.accumulated_transform = transform * self.accumulated_transform;
+    }
+}
+
+impl Draw for BarChart {
+    fn draw(&self, ctx: &mut Context) {
+        self.bars.iter().for_each(|m| m.draw(ctx));
+        self.axes.iter().for_each(|m| m.draw(ctx));
+        self.label_texts.iter().for_each(|m| m.draw(ctx));
+    }
+}
+
+impl Mobject for BarChart {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(
+            self.axes
+                .iter()
+                .map(|m| m.bounding_box())
+                .chain(self.bars.iter().map(|m| m.bounding_box()))
+                .chain(self.label_texts.iter().map(|m| m.bounding_box())),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct LineChart {
+    pub size: (GMFloat, GMFloat),
+    /// value drawn at the top of the plotting area, None fits the largest value
+    pub y_max: Option<GMFloat>,
+    pub draw_config: DrawConfig,
+    labels: Vec<String>,
+    values: Vec<GMFloat>,
+    axes: Vec<SimpleLine>,
+    line: PolyLine,
+    label_texts: Vec<Text>,
+    /// slot count `label_texts` were laid out for
+    label_slots: usize,
+    accumulated_transform: nalgebra::Transform3<GMFloat>,
+}
+
+impl LineChart {
+    pub fn from_data(
+        table: &DataTable,
+        label_column: &str,
+        value_column: &str,
+    ) -> Result<Self, DataError> {
+        let (values, labels) = read_series(table, label_column, value_column)?;
+        Ok(Self::new(values, labels))
+    }
+
+    pub fn new(values: Vec<GMFloat>, labels: Vec<String>) -> Self {
+        let size = DEFAULT_CHART_SIZE;
+        let draw_config = DrawConfig {
+            stroke_width: 0.08,
+            ..Default::default()
+        };
+        let mut chart = Self {
+            y_max: None,
+            axes: build_axes(size),
+            label_texts: vec![],
+            label_slots: 0,
+            labels,
+            size,
+            draw_config: draw_config.clone(),
+            values: vec![],
+            line: PolyLine {
+                points: vec![],
+                draw_config,
+            },
+            accumulated_transform: nalgebra::Transform3::identity(),
+        };
+        chart.set_values(&values);
+        chart
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// relabel the slots, one slot is added per label beyond the values
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        self.labels = labels;
+        // forces the labels to be rebuilt
+        self.label_slots = 0;
+        let values = self.values.clone();
+        self.set_values(&values);
+    }
+
+    fn slots(&self) -> usize {
+        self.values.len().max(self.labels.len())
+    }
+}
+
+impl DataChart for LineChart {
+    fn values(&self) -> &[GMFloat] {
+        &self.values
+    }
+    fn interpolate_values(&mut self, from: &[GMFloat], to: &[GMFloat], t: GMFloat) {
+        self.values = lerp_values(from, to, t);
+        let y_max = fit_y_max(self.y_max, from, to);
+        let slots = self.slots();
+        let bottom = -self.size.1 / 2.0;
+        let mut line = PolyLine {
+            points: self
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    Point3::new(
+                        slot_x(self.size, i, slots),
+                        bottom + v / y_max * self.size.1,
+                        0.0,
+                    )
+                })
+                .collect(),
+            draw_config: self.draw_config.clone(),
+        };
+        line.transform(self.accumulated_transform);
+        self.line = line;
+        if self.label_slots != slots {
+            self.label_slots = slots;
+            self.label_texts =
+                build_x_labels(self.size, &self.labels, slots, self.accumulated_transform);
+        }
+    }
+}
+
+impl Transform for LineChart {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.axes.iter_mut().for_each(|m| m.transform(transform));
+        self.line.transform(transform);
+        self.label_texts
+            .iter_mut()
+            .for_each(|m| m.transform(transform));
+        self.accumulated_transform = transform * self.accumulated_transform;
+    }
+}
+
+impl Draw for LineChart {
+    fn draw(&self, ctx: &mut Context) {
+        self.axes.iter().for_each(|m| m.draw(ctx));
+        self.line.draw(ctx);
+        self.label_texts.iter().for_each(|m| m.draw(ctx));
+    }
+}
+
+impl Mobject for LineChart {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(
+            self.axes
+                .iter()
+                .map(|m| m.bounding_box())
+                .chain([self.line.bounding_box()])
+                .chain(self.label_texts.iter().map(|m| m.bounding_box())),
+        )
+    }
+}
+
+/// pie centered at origin with a legend of colored squares and labels on its right
+#[derive(Clone)]
+pub struct PieChart {
+    pub radius: GMFloat,
+    labels: Vec<String>,
+    values: Vec<GMFloat>,
+    sectors: Vec<Polygon>,
+    legend: Vec<Polygon>,
+    label_texts: Vec<Text>,
+    accumulated_transform: nalgebra::Transform3<GMFloat>,
+}
+
+impl PieChart {
+    pub fn from_data(
+        table: &DataTable,
+        label_column: &str,
+        value_column: &str,
+    ) -> Result<Self, DataError> {
+        let (values, labels) = read_series(table, label_column, value_column)?;
+        Ok(Self::new(values, labels))
+    }
+
+    pub fn new(values: Vec<GMFloat>, labels: Vec<String>) -> Self {
+        let radius = DEFAULT_CHART_SIZE.1 / 2.0;
+        let (legend, label_texts) = Self::build_legend(radius, &labels);
+        let mut chart = Self {
+            labels,
+            radius,
+            values: vec![],
+            sectors: vec![],
+            legend,
+            label_texts,
+            accumulated_transform: nalgebra::Transform3::identity(),
+        };
+        chart.set_values(&values);
+        chart
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        let (mut legend, mut label_texts) = Self::build_legend(self.radius, &labels);
+        legend
+            .iter_mut()
+            .for_each(|m| m.transform(self.accumulated_transform));
+        label_texts
+            .iter_mut()
+            .for_each(|m| m.transform(self.accumulated_transform));
+        (self.labels, self.legend, self.label_texts) = (labels, legend, label_texts);
+    }
+
+    /// colored squares with their labels in a column right of the pie
+    fn build_legend(radius: GMFloat, labels: &[String]) -> (Vec<Polygon>, Vec<Text>) {
+        let legend_x = radius + 1.0;
+        let row_height = DEFAULT_LABEL_FONT_SIZE * 0.1 + LABEL_BUFFER;
+        let top = row_height * (labels.len() as GMFloat - 1.0) / 2.0;
+        let mut legend = vec![];
+        let mut label_texts = vec![];
+        for (i, l) in labels.iter().enumerate() {
+            let y = top - row_height * i as GMFloat;
+            let half = row_height / 3.0;
+            let mut square = Polygon::new(vec![
+                Point3::new(legend_x - half, y - half, 0.0),
+                Point3::new(legend_x + half, y - half, 0.0),
+                Point3::new(legend_x + half, y + half, 0.0),
+                Point3::new(legend_x - half, y + half, 0.0),
+            ]);
+            square.draw_config.fill_color = chart_color(i);
+            legend.push(square);
+            label_texts.push(build_label(
+                l,
+                Point3::new(legend_x + half + LABEL_BUFFER, y, 0.0),
+                (0.0, 0.5),
+            ));
+        }
+        (legend, label_texts)
+    }
+}
+
+impl DataChart for PieChart {
+    fn values(&self) -> &[GMFloat] {
+        &self.values
+    }
+    fn interpolate_values(&mut self, from: &[GMFloat], to: &[GMFloat], t: GMFloat) {
+        self.values = lerp_values(from, to, t);
+        let total: GMFloat = self.values.iter().map(|v| v.max(0.0)).sum();
+        if total <= 0.0 {
+            self.sectors = vec![];
+            return;
+        }
+        // start at twelve o'clock and go clockwise
+        let mut start_angle = PI / 2.0;
+        self.sectors = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let sweep = v.max(0.0) / total * 2.0 * PI;
+                let segments =
+                    ((sweep / (2.0 * PI) * PIE_SEGMENTS as GMFloat).ceil() as usize).max(1);
+                let mut vertices = vec![Point3::origin()];
+                vertices.extend((0..=segments).map(|s| {
+                    let angle = start_angle - sweep * s as GMFloat / segments as GMFloat;
+                    Point3::origin() + Vector3::new(angle.cos(), angle.sin(), 0.0) * self.radius
+                }));
+                start_angle -= sweep;
+                let mut sector = Polygon::new(vertices);
+                sector.draw_config.fill_color = chart_color(i);
+                sector.transform(self.accumulated_transform);
+                sector
+            })
+            .collect();
+    }
+}
+
+impl Transform for PieChart {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.sectors.iter_mut().for_each(|m| m.transform(transform));
+        self.legend.iter_mut().for_each(|m| m.transform(transform));
+        self.label_texts
+            .iter_mut()
+            .for_each(|m| m.transform(transform));
+        self.accumulated_transform = transform * self.accumulated_transform;
+    }
+}
+
+impl Draw for PieChart {
+    fn draw(&self, ctx: &mut Context) {
+        self.sectors.iter().for_each(|m| m.draw(ctx));
+        self.legend.iter().for_each(|m| m.draw(ctx));
+        self.label_texts.iter().for_each(|m| m.draw(ctx));
+    }
+}
+
+impl Mobject for PieChart {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(
+            self.sectors
+                .iter()
+                .map(|m| m.bounding_box())
+                .chain(self.legend.iter().map(|m| m.bounding_box()))
+                .chain(self.label_texts.iter().map(|m| m.bounding_box())),
+        )
+    }
+}
+
+#[test]
+fn test_charts() {
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let labels: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+    let mut bar_chart = BarChart::new(vec![1.0, 3.0, 2.0, 4.0], labels.clone());
+    bar_chart.scale(0.4);
+    bar_chart.move_this(Vector3::new(-4.5, 2.0, 0.0));
+    bar_chart.set_values(&[4.0, 2.0, 3.0, 1.0]);
+    assert_eq!(bar_chart.values(), &[4.0, 2.0, 3.0, 1.0]);
+    // larger values rescale instead of leaving the plot area
+    let height = bar_chart.bounding_box().unwrap().height();
+    bar_chart.set_values(&[8.0, 2.0, 3.0, 1.0]);
+    assert!((bar_chart.bounding_box().unwrap().height() - height).abs() < 1e-4);
+    bar_chart.y_max = Some(16.0);
+    bar_chart.set_values(&[8.0, 2.0, 3.0, 1.0]);
+    let b = bar_chart.bars[0].bounding_box().unwrap();
+    assert!((b.height() - 0.4 * DEFAULT_CHART_SIZE.1 / 2.0).abs() < 1e-4);
+    bar_chart.y_max = None;
+    // a longer series gets a slot and a label per entry
+    bar_chart.set_labels(["a", "b", "c", "d", "e"].map(String::from).to_vec());
+    bar_chart.set_values(&[4.0, 2.0, 3.0, 1.0, 2.0]);
+    assert_eq!(bar_chart.label_texts.len(), 5);
+    let last = bar_chart.label_texts[4].bounding_box().unwrap();
+    assert!(last.max.x < bar_chart.axes[0].bounding_box().unwrap().max.x);
+    let mut line_chart = LineChart::new(vec![1.0, 3.0, 2.0, 4.0], labels.clone());
+    line_chart.scale(0.4);
+    line_chart.move_this(Vector3::new(2.5, 2.0, 0.0));
+    let table = DataTable::from_csv_str("label,value\na,1\nb,3\nc,2\nd,4\n").unwrap();
+    let mut pie_chart = PieChart::from_data(&table, "label", "value").unwrap();
+    let spaced = DataTable::from_csv_str("label, value\nnorth, 1\n south, 2").unwrap();
+    let spaced_chart = BarChart::from_data(&spaced, "label", "value").unwrap();
+    assert_eq!(spaced_chart.labels()[1], "south");
+    pie_chart.scale(0.6);
+    pie_chart.move_this(Vector3::new(0.0, -2.0, 0.0));
+    scene.add(Box::new(bar_chart));
+    scene.add(Box::new(line_chart));
+    scene.add(Box::new(pie_chart));
+    scene.save_png(&mut ctx, "charts.png");
+}
@@ -0,0 +1,400 @@
+use nalgebra::Vector3;
+use tiny_skia::{BlendMode, Pixmap, PixmapPaint};
+
+use crate::color::{self, Color, ColorSpace};
+use crate::{Context, ContextType, GMFloat, SceneConfig};
+
+use super::{BoundingBox, Draw, Mobject, Transform};
+
+/// raster effect run on an offscreen layer, radii and offsets are in scene units
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    /// gaussian blur, `radius` is the standard deviation
+    Blur { radius: GMFloat },
+    /// blurred copy in `color` behind the drawing, `strength` above 1 makes it brighter
+    Glow {
+        radius: GMFloat,
+        color: Color,
+        strength: GMFloat,
+    },
+    /// blurred copy in `color` moved by `offset` behind the drawing, the alpha of
+    /// `color` is the opacity of the shadow
+    DropShadow {
+        offset: Vector3<GMFloat>,
+        radius: GMFloat,
+        color: Color,
+    },
+}
+
+impl Effect {
+    pub fn blur(radius: GMFloat) -> Self {
+        Effect::Blur { radius }
+    }
+
+    pub fn glow(radius: GMFloat, color: Color) -> Self {
+        Effect::Glow {
+            radius,
+            color,
+            strength: 1.0,
+        }
+    }
+
+    /// half transparent black shadow
+    pub fn drop_shadow(offset: Vector3<GMFloat>, radius: GMFloat) -> Self {
+        Effect::DropShadow {
+            offset,
+            radius,
+            color: color::BLACK.with_alpha(0x80),
+        }
+    }
+
+    /// the same effect at zero strength, drawing looks as without it
+    pub fn neutral(&self) -> Self {
+        match self {
+            Effect::Blur { .. } => Effect::Blur { radius: 0.0 },
+            Effect::Glow { radius, color, .. } => Effect::Glow {
+                radius: *radius,
+                color: *color,
+                strength: 0.0,
+            },
+            Effect::DropShadow {
+                offset,
+                radius,
+                color,
+            } => Effect::DropShadow {
+                offset: *offset,
+                radius: *radius,
+                color: color.with_alpha(0),
+            },
+        }
+    }
+
+    /// `t` of the way to `other`, effects of different kinds switch halfway
+    pub fn interpolate(&self, other: &Effect, t: GMFloat) -> Self {
+        let lerp = |a: GMFloat, b: GMFloat| a + (b - a) * t;
+        let lerp_color = |a: &Color, b: &Color| a.interpolate(b, t, ColorSpace::Srgb);
+        match (self, other) {
+            (Effect::Blur { radius: r0 }, Effect::Blur { radius: r1 }) => Effect::Blur {
+                radius: lerp(*r0, *r1),
+            },
+            (
+                Effect::Glow {
+                    radius: r0,
+                    color: c0,
+                    strength: s0,
+                },
+                Effect::Glow {
+                    radius: r1,
+                    color: c1,
+                    strength: s1,
+                },
+            ) => Effect::Glow {
+                radius: lerp(*r0, *r1),
+                color: lerp_color(c0, c1),
+                strength: lerp(*s0, *s1),
+            },
+            (
+                Effect::DropShadow {
+                    offset: o0,
+                    radius: r0,
+                    color: c0,
+                },
+                Effect::DropShadow {
+                    offset: o1,
+                    radius: r1,
+                    color: c1,
+                },
+            ) => Effect::DropShadow {
+                offset: o0.lerp(o1, t),
+                radius: lerp(*r0, *r1),
+                color: lerp_color(c0, c1),
+            },
+            _ if t < 0.5 => self.clone(),
+            _ => other.clone(),
+        }
+    }
+
+    /// run on `pixmap` in place, `pixmap` holds only what the effect applies to
+    pub fn apply(&self, pixmap: &mut Pixmap, scene_config: &SceneConfig) {
+        let s = scene_config.scale_factor;
+        match self {
+            Effect::Blur { radius } => gaussian_blur(pixmap, radius * s),
+            Effect::Glow {
+                radius,
+                color,
+                strength,
+            } => {
+                let mut glow = silhouette(pixmap, color, *strength);
+                gaussian_blur(&mut glow, radius * s);
+                draw_behind(pixmap, &glow, 0.0, 0.0);
+            }
+            Effect::DropShadow {
+                offset,
+                radius,
+                color,
+            } => {
+                let mut shadow = silhouette(pixmap, color, 1.0);
+                gaussian_blur(&mut shadow, radius * s);
+                // scene y points up, pixel y points down
+                draw_behind(pixmap, &shadow, offset.x * s, -offset.y * s);
+            }
+        }
+    }
+}
+
+/// `effects` in order on what was drawn since the last `Context::push_layer`
+pub fn apply_effects(effects: &[Effect], ctx: &mut Context) {
+    if let ContextType::TinySKIA(pixmap) = &mut ctx.ctx_type {
+        for effect in effects {
+            effect.apply(pixmap, &ctx.scene_config);
+        }
+    }
+}
+
+/// each effect `t` of the way from `from` to `to`, missing entries count as neutral
+pub fn lerp_effects(from: &[Effect], to: &[Effect], t: GMFloat) -> Vec<Effect> {
+    (0..from.len().max(to.len()))
+        .map(|i| match (from.get(i), to.get(i)) {
+            (Some(a), Some(b)) => a.interpolate(b, t),
+            (Some(a), None) => a.interpolate(&a.neutral(), t),
+            (None, Some(b)) => b.neutral().interpolate(b, t),
+            (None, None) => unreachable!(),
+        })
+        .collect()
+}
+
+/// the coverage of `pixmap` filled with `color`, alpha scaled by `strength`
+fn silhouette(pixmap: &Pixmap, color: &Color, strength: GMFloat) -> Pixmap {
+    let mut out = pixmap.clone();
+    let alpha = color.a as GMFloat / 255.0 * strength.max(0.0);
+    for px in out.data_mut().chunks_exact_mut(4) {
+        let a = (px[3] as GMFloat * alpha).min(255.0).round();
+        let channel = |c: u8| (c as GMFloat * a / 255.0).round() as u8;
+        px.copy_from_slice(&[
+            channel(color.r),
+            channel(color.g),
+            channel(color.b),
+            a as u8,
+        ]);
+    }
+    out
+}
+
+/// `pixmap` drawn over `below` moved by a pixel offset, the result kept in `pixmap`
+fn draw_behind(pixmap: &mut Pixmap, below: &Pixmap, dx: GMFloat, dy: GMFloat) {
+    let mut out = below.clone();
+    if dx != 0.0 || dy != 0.0 {
+        out.fill(tiny_skia::Color::TRANSPARENT);
+        out.draw_pixmap(
+            0,
+            0,
+            below.as_ref(),
+            &PixmapPaint::default(),
+            tiny_skia::Transform::from_translate(dx, dy),
+            None,
+        );
+    }
+    out.draw_pixmap(
+        0,
+        0,
+        pixmap.as_ref(),
+        &PixmapPaint::default(),
+        tiny_skia::Transform::identity(),
+        None,
+    );
+    *pixmap = out;
+}
+
+/// three box blurs in a row, close to a gaussian with deviation `sigma` in pixels
+pub fn gaussian_blur(pixmap: &mut Pixmap, sigma: GMFloat) {
+    if sigma < 0.5 {
+        return;
+    }
+    let (w, h) = (pixmap.width() as usize, pixmap.height() as usize);
+    let mut buffer = pixmap.data().to_vec();
+    for size in box_sizes(sigma, 3) {
+        let r = (size - 1) / 2;
+        box_blur(pixmap.data(), &mut buffer, h, w, 4, w * 4, r);
+        box_blur(&buffer, pixmap.data_mut(), w, h, w * 4, 4, r);
+    }
+}
+
+/// odd box widths whose repeated blur matches a gaussian, see
+/// "Fast Almost-Gaussian Filtering" by Peter Kovesi
+fn box_sizes(sigma: GMFloat, n: usize) -> Vec<usize> {
+    let nf = n as GMFloat;
+    let ideal = (12.0 * sigma * sigma / nf + 1.0).sqrt();
+    let mut lower = ideal.floor() as usize;
+    if lower.is_multiple_of(2) {
+        lower -= 1;
+    }
+    let l = lower as GMFloat;
+    let m = ((12.0 * sigma * sigma - nf * l * l - 4.0 * nf * l - 3.0 * nf) / (-4.0 * l - 4.0))
+        .round() as usize;
+    (0..n)
+        .map(|i| if i < m { lower } else { lower + 2 })
+        .collect()
+}
+
+/// average over `2r+1` samples along each of `lines` runs of `length` pixels, pixels
+/// outside count as transparent; works on premultiplied rgba so colors don't bleed
+fn box_blur(
+    src: &[u8],
+    dst: &mut [u8],
+    lines: usize,
+    length: usize,
+    step: usize,
+    line_step: usize,
+    r: usize,
+) {
+    let div = 2 * r as u32 + 1;
+    for line in 0..lines {
+        let start = line * line_step;
+        for c in 0..4 {
+            let sample = |i: isize| match i {
+                i if i < 0 || i >= length as isize => 0,
+                i => src[start + i as usize * step + c] as u32,
+            };
+            let r = r as isize;
+            let mut sum: u32 = (-r..=r).map(sample).sum();
+            for i in 0..length as isize {
+                dst[start + i as usize * step + c] = ((sum + div / 2) / div) as u8;
+                sum += sample(i + r + 1);
+                sum -= sample(i - r);
+            }
+        }
+    }
+}
+
+/// `content` drawn with `effects` applied in order, e.g. a glowing formula or a card
+/// with a drop shadow
+///
+/// the effect parameters are plain fields, change them between frames or use
+/// `animation::EffectTransition` to animate them
+#[derive(Clone)]
+pub struct Effected {
+    pub content: Box<dyn Mobject>,
+    pub effects: Vec<Effect>,
+}
+
+impl Effected {
+    pub fn new(content: Box<dyn Mobject>, effects: Vec<Effect>) -> Self {
+        Self { content, effects }
+    }
+
+    /// show `from` moved `t` of the way to `to`, see [`lerp_effects`]
+    pub fn interpolate_effects(&mut self, from: &[Effect], to: &[Effect], t: GMFloat) {
+        self.effects = lerp_effects(from, to, t);
+    }
+}
+
+impl Transform for Effected {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.content.transform(transform);
+    }
+}
+
+impl Draw for Effected {
+    fn draw(&self, ctx: &mut Context) {
+        if self.effects.is_empty() {
+            self.content.draw(ctx);
+            return;
+        }
+        ctx.push_layer();
+        self.content.draw(ctx);
+        apply_effects(&self.effects, ctx);
+        ctx.pop_layer(1.0, BlendMode::SourceOver);
+    }
+}
+
+impl Mobject for Effected {
+    /// the content alone, blur and shadows reach a bit further
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.content.bounding_box()
+    }
+
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        vec![self.content.as_ref()]
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        vec![self.content.as_mut()]
+    }
+}
+
+#[test]
+fn test_effects() {
+    use super::{DrawConfig, Rectangle};
+    use crate::{Background, Scene};
+
+    let square = |x: GMFloat| {
+        let mut square = Rectangle {
+            draw_config: DrawConfig::filled(color::WHITE),
+            ..Default::default()
+        };
+        square.scale(2.0);
+        square.move_this(Vector3::new(x - 1.0, -1.0, 0.0));
+        Box::new(square)
+    };
+    let mut ctx = Context::default();
+    let mut scene = Scene {
+        background: Some(Background::Color(color::GRAY)),
+        ..Default::default()
+    };
+    scene.add(Box::new(Effected::new(
+        square(-4.5),
+        vec![Effect::blur(0.3)],
+    )));
+    scene.add(Box::new(Effected::new(
+        square(0.0),
+        vec![Effect::glow(0.3, color::YELLOW)],
+    )));
+    scene.add(Box::new(Effected::new(
+        square(4.5),
+        vec![Effect::drop_shadow(Vector3::new(0.3, -0.3, 0.0), 0.1)],
+    )));
+    scene.save_png(&mut ctx, "effects.png");
+
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let rgb_at = |x: GMFloat, y: GMFloat| {
+        let p = pixmap
+            .pixel(
+                ctx.scene_config.convert_coord_x(x) as u32,
+                ctx.scene_config.convert_coord_y(y) as u32,
+            )
+            .unwrap();
+        (p.red(), p.green(), p.blue())
+    };
+    let gray = (color::GRAY.r, color::GRAY.g, color::GRAY.b);
+    // the blurred edge sits between the square and the background
+    assert_eq!(rgb_at(-4.5, 0.0), (0xff, 0xff, 0xff));
+    let (edge, _, _) = rgb_at(-3.5, 0.0);
+    assert!(edge > gray.0 + 0x20 && edge < 0xff - 0x20);
+    assert_eq!(rgb_at(-4.5, 2.5), gray);
+    // yellow around the square, the square itself untouched
+    assert_eq!(rgb_at(0.0, 0.0), (0xff, 0xff, 0xff));
+    let (r, g, b) = rgb_at(1.2, 0.0);
+    assert!(r > gray.0 && g > gray.1 && b <= gray.2);
+    // darker below right of the square only
+    assert!(rgb_at(5.7, -1.15).0 < gray.0 - 0x20);
+    assert_eq!(rgb_at(3.3, 1.15), gray);
+
+    // a missing effect fades in from neutral
+    let half = lerp_effects(&[], &[Effect::blur(0.4)], 0.5);
+    assert_eq!(half, vec![Effect::blur(0.2)]);
+    let shadow = Effect::drop_shadow(Vector3::zeros(), 0.1);
+    assert_eq!(shadow.interpolate(&shadow.neutral(), 1.0), shadow.neutral());
+
+    // a full frame blur softens the sharp square too, the background stays flat
+    scene.effects = vec![Effect::blur(0.3)];
+    scene.render(&mut ctx);
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let x = ctx.scene_config.convert_coord_x(5.5) as u32;
+    let y = ctx.scene_config.convert_coord_y(0.0) as u32;
+    assert!(pixmap.pixel(x, y).unwrap().red() < 0xf0);
+    assert_eq!(pixmap.pixel(0, 0).unwrap().red(), gray.0);
+}
@@ -0,0 +1,174 @@
+use nalgebra::Point3;
+use tiny_skia::{LinearGradient, RadialGradient, Shader, SpreadMode};
+
+use crate::{Color, GMFloat, SceneConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// 0 at the start of the gradient, 1 at its end
+    pub offset: GMFloat,
+    pub color: Color,
+}
+
+impl GradientStop {
+    pub fn new(offset: GMFloat, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    Linear {
+        start: Point3<GMFloat>,
+        end: Point3<GMFloat>,
+    },
+    /// circles growing from `focal` (radius 0) to `center` (radius `radius`)
+    Radial {
+        center: Point3<GMFloat>,
+        focal: Point3<GMFloat>,
+        radius: GMFloat,
+    },
+}
+
+/// gradient paint for a fill or a stroke, placed in scene coordinates
+///
+/// `transform` collects the transforms of the mobject it belongs to, so the
+/// gradient moves, scales and rotates with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<GradientStop>,
+    /// what happens past the first and the last stop
+    pub spread: SpreadMode,
+    pub transform: nalgebra::Transform3<GMFloat>,
+}
+
+impl Gradient {
+    pub fn linear(start: Point3<GMFloat>, end: Point3<GMFloat>, stops: Vec<GradientStop>) -> Self {
+        Self {
+            kind: GradientKind::Linear { start, end },
+            stops,
+            spread: SpreadMode::Pad,
+            transform: nalgebra::Transform3::identity(),
+        }
+    }
+
+    pub fn radial(center: Point3<GMFloat>, radius: GMFloat, stops: Vec<GradientStop>) -> Self {
+        Self {
+            kind: GradientKind::Radial {
+                center,
+                focal: center,
+                radius,
+            },
+            stops,
+            spread: SpreadMode::Pad,
+            transform: nalgebra::Transform3::identity(),
+        }
+    }
+
+    /// tiny-skia shader drawing in pixel coordinates, None when it can't be built,
+    /// e.g. without stops or with zero length
+    pub(crate) fn shader(
+        &self,
+        opacity: GMFloat,
+        scene_config: &SceneConfig,
+    ) -> Option<Shader<'static>> {
+        let stops = self
+            .stops
+            .iter()
+            .map(|s| {
+                let mut color: tiny_skia::Color = s.color.into();
+                color.apply_opacity(opacity.clamp(0.0, 1.0));
+                tiny_skia::GradientStop::new(s.offset, color)
+            })
+            .collect();
+        let transform = self.pixel_transform(scene_config);
+        let point = |p: &Point3<GMFloat>| tiny_skia::Point::from_xy(p.x, p.y);
+        match &self.kind {
+            GradientKind::Linear { start, end } => {
+                LinearGradient::new(point(start), point(end), stops, self.spread, transform)
+            }
+            GradientKind::Radial {
+                center,
+                focal,
+                radius,
+            } => RadialGradient::new(
+                point(focal),
+                0.0,
+                point(center),
+                *radius,
+                stops,
+                self.spread,
+                transform,
+            ),
+        }
+    }
+
+    /// gradient space to pixels, the xy part of `transform` followed by the scene's
+    /// flip and scale
+    fn pixel_transform(&self, scene_config: &SceneConfig) -> tiny_skia::Transform {
+        let m = self.transform.matrix();
+        let s = scene_config.scale_factor;
+        tiny_skia::Transform::from_row(
+            s * m[(0, 0)],
+            -s * m[(1, 0)],
+            s * m[(0, 1)],
+            -s * m[(1, 1)],
+            s * (scene_config.width / 2.0 + m[(0, 3)]),
+            s * (scene_config.height / 2.0 - m[(1, 3)]),
+        )
+    }
+}
+
+#[test]
+fn test_gradients() {
+    use super::{Mobject, Rectangle, Transform};
+    use crate::{Context, Scene};
+
+    let red = Color::new(0xff, 0x00, 0x00, 0xff);
+    let blue = Color::new(0x00, 0x00, 0xff, 0xff);
+    let mut rectangle = Rectangle {
+        draw_config: super::DrawConfig::default()
+            .with_fill_gradient(Gradient::linear(
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                vec![GradientStop::new(0.0, red), GradientStop::new(1.0, blue)],
+            ))
+            .with_stroke_gradient(Gradient::radial(
+                Point3::new(0.5, 0.5, 0.0),
+                0.75,
+                vec![GradientStop::new(0.0, blue), GradientStop::new(1.0, red)],
+            )),
+        ..Default::default()
+    };
+    rectangle.scale(3.0);
+    rectangle.move_this(nalgebra::Vector3::new(-5.0, -1.0, 0.0));
+    // the gradient end follows the right edge of the rectangle
+    let g = rectangle.draw_config.fill_gradient.as_ref().unwrap();
+    let GradientKind::Linear { end, .. } = g.kind else {
+        unreachable!()
+    };
+    assert_eq!(g.transform * end, Point3::new(-2.0, -1.0, 0.0));
+
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="40">
+        <defs>
+            <linearGradient id="fade">
+                <stop offset="0" stop-color="#ffff00"/>
+                <stop offset="1" stop-color="#00ff00" stop-opacity="0.5"/>
+            </linearGradient>
+        </defs>
+        <rect id="box" x="0" y="0" width="40" height="20" fill="url(#fade)"/>
+    </svg>"##;
+    std::fs::write("gradient.svg", svg).unwrap();
+    let mut shape = super::svg_shape::open_svg_file("gradient.svg");
+    shape.move_this(nalgebra::Vector3::new(1.0, 1.0, 0.0));
+    let imported = shape.find_by_name("box").unwrap().draw_config().unwrap();
+    let g = imported.fill_gradient.as_ref().unwrap();
+    assert_eq!(g.stops[1].color, Color::new(0x00, 0xff, 0x00, 0x80));
+
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    scene.add(Box::new(rectangle));
+    scene.add(Box::new(shape));
+    scene.save_png(&mut ctx, "gradients.png");
+}
@@ -0,0 +1,305 @@
+use nalgebra::Point3;
+
+use crate::{Color, Context, GMFloat, GMPoint, Scene};
+
+use super::{polygon::Polygon, BoundingBox, Draw, DrawConfig, Mobject, PolyLine, Transform};
+
+pub const DEFAULT_GRAPH_SAMPLES: usize = 100;
+
+#[derive(Clone)]
+pub struct FunctionGraph {
+    pub function: fn(GMFloat) -> GMFloat,
+    pub x_range: (GMFloat, GMFloat),
+    pub points: Vec<GMPoint>,
+    pub draw_config: DrawConfig,
+}
+
+impl FunctionGraph {
+    pub fn new(function: fn(GMFloat) -> GMFloat, x_range: (GMFloat, GMFloat)) -> Self {
+        Self {
+            function,
+            x_range,
+            points: sample_function(function, x_range, DEFAULT_GRAPH_SAMPLES),
+            draw_config: DrawConfig::default(),
+        }
+    }
+}
+
+/// sample `samples` segments of y = f(x) over x_range, both ends included
+pub fn sample_function(
+    function: fn(GMFloat) -> GMFloat,
+    x_range: (GMFloat, GMFloat),
+    samples: usize,
+) -> Vec<GMPoint> {
+    let samples = samples.max(1);
+    let dx = (x_range.1 - x_range.0) / samples as GMFloat;
+    (0..=samples)
+        .map(|i| {
+            let x = x_range.0 + i as GMFloat * dx;
+            Point3::new(x, function(x), 0.0)
+        })
+        .collect()
+}
+
+impl Transform for FunctionGraph {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        for p in &mut self.points {
+            *p = transform * (*p);
+        }
+        self.draw_config.transform_gradients(transform);
+    }
+}
+
+impl Draw for FunctionGraph {
+    fn draw(&self, ctx: &mut Context) {
+        PolyLine {
+            points: self.points.clone(),
+            draw_config: self.draw_config.clone(),
+        }
+        .draw(ctx);
+    }
+}
+
+impl Mobject for FunctionGraph {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.points.iter().copied())
+    }
+
+    fn control_points(&self) -> Vec<GMPoint> {
+        self.points.clone()
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        self.points = points.to_vec();
+    }
+}
+
+fn default_area_draw_config() -> DrawConfig {
+    DrawConfig::filled(crate::theme::theme().primary.with_alpha(0x80))
+}
+
+/// shaded region between two sampled curves
+#[derive(Clone)]
+pub struct Area {
+    region: Polygon,
+}
+
+impl Area {
+    /// region between y = f(x) and the x axis
+    pub fn under_graph(function: fn(GMFloat) -> GMFloat, x_range: (GMFloat, GMFloat)) -> Self {
+        Self::between_graphs(function, |_| 0.0, x_range)
+    }
+
+    /// region between y = upper(x) and y = lower(x), the curves may cross
+    pub fn between_graphs(
+        upper: fn(GMFloat) -> GMFloat,
+        lower: fn(GMFloat) -> GMFloat,
+        x_range: (GMFloat, GMFloat),
+    ) -> Self {
+        let mut vertices = sample_function(upper, x_range, DEFAULT_GRAPH_SAMPLES);
+        vertices.extend(
+            sample_function(lower, x_range, DEFAULT_GRAPH_SAMPLES)
+                .into_iter()
+                .rev(),
+        );
+        let mut region = Polygon::new(vertices);
+        region.draw_config = default_area_draw_config();
+        Self { region }
+    }
+
+    pub fn set_draw_config(&mut self, draw_config: DrawConfig) {
+        self.region.draw_config = draw_config;
+    }
+}
+
+impl Transform for Area {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.region.transform(transform);
+    }
+}
+
+impl Draw for Area {
+    fn draw(&self, ctx: &mut Context) {
+        self.region.draw(ctx);
+    }
+}
+
+impl Mobject for Area {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.region.bounding_box()
+    }
+
+    fn control_points(&self) -> Vec<GMPoint> {
+        self.region.control_points()
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        self.region.set_control_points(points);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RiemannSumType {
+    Left,
+    Right,
+    Midpoint,
+}
+
+impl RiemannSumType {
+    fn sample_x(&self, left: GMFloat, right: GMFloat) -> GMFloat {
+        match self {
+            RiemannSumType::Left => left,
+            RiemannSumType::Right => right,
+            RiemannSumType::Midpoint => (left + right) / 2.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RiemannRectangles {
+    pub function: fn(GMFloat) -> GMFloat,
+    pub x_range: (GMFloat, GMFloat),
+    pub sum_type: RiemannSumType,
+    pub draw_config: DrawConfig,
+    partition: usize,
+    rectangles: Vec<Polygon>,
+    // transforms applied so far, replayed whenever the rectangles are rebuilt
+    accumulated_transform: nalgebra::Transform3<GMFloat>,
+}
+
+impl RiemannRectangles {
+    pub fn new(
+        function: fn(GMFloat) -> GMFloat,
+        x_range: (GMFloat, GMFloat),
+        partition: usize,
+        sum_type: RiemannSumType,
+    ) -> Self {
+        let mut r = Self {
+            function,
+            x_range,
+            sum_type,
+            draw_config: default_area_draw_config(),
+            partition,
+            rectangles: vec![],
+            accumulated_transform: nalgebra::Transform3::identity(),
+        };
+        r.set_partition(partition);
+        r
+    }
+
+    pub fn partition(&self) -> usize {
+        self.partition
+    }
+
+    pub fn set_partition(&mut self, partition: usize) {
+        self.interpolate_partition(partition, partition, 1.0);
+    }
+
+    /// approximated sum of the rectangle areas
+    pub fn sum(&self) -> GMFloat {
+        let dx = self.dx(self.partition);
+        (0..self.partition)
+            .map(|i| self.height(self.partition, i) * dx)
+            .sum()
+    }
+
+    /// rebuild the rectangles of `to_partition`, with heights moved `t` of the way
+    /// from the rectangle of `from_partition` that covers them
+    pub fn interpolate_partition(
+        &mut self,
+        from_partition: usize,
+        to_partition: usize,
+        t: GMFloat,
+    ) {
+        let to_partition = to_partition.max(1);
+        let from_partition = from_partition.max(1);
+        let dx = self.dx(to_partition);
+        let from_dx = self.dx(from_partition);
+        self.rectangles = (0..to_partition)
+            .map(|i| {
+                let left = self.x_range.0 + i as GMFloat * dx;
+                let right = left + dx;
+                let from_index = ((((left + right) / 2.0 - self.x_range.0) / from_dx) as usize)
+                    .min(from_partition - 1);
+                let height = self.height(from_partition, from_index) * (1.0 - t)
+                    + self.height(to_partition, i) * t;
+                let mut rectangle = Polygon::new(vec![
+                    Point3::new(left, 0.0, 0.0),
+                    Point3::new(right, 0.0, 0.0),
+                    Point3::new(right, height, 0.0),
+                    Point3::new(left, height, 0.0),
+                ]);
+                rectangle.draw_config = DrawConfig {
+                    fill_color: shade(self.draw_config.fill_color, i, to_partition),
+                    ..self.draw_config.clone()
+                };
+                rectangle.transform(self.accumulated_transform);
+                rectangle
+            })
+            .collect();
+        self.partition = to_partition;
+    }
+
+    fn dx(&self, partition: usize) -> GMFloat {
+        (self.x_range.1 - self.x_range.0) / partition as GMFloat
+    }
+
+    fn height(&self, partition: usize, index: usize) -> GMFloat {
+        let dx = self.dx(partition);
+        let left = self.x_range.0 + index as GMFloat * dx;
+        (self.function)(self.sum_type.sample_x(left, left + dx))
+    }
+}
+
+// darken every other rectangle so neighbours stay distinguishable without a stroke
+fn shade(color: Color, index: usize, count: usize) -> Color {
+    if count < 2 || index.is_multiple_of(2) {
+        return color;
+    }
+    Color::new(
+        (color.r as u16 * 4 / 5) as u8,
+        (color.g as u16 * 4 / 5) as u8,
+        (color.b as u16 * 4 / 5) as u8,
+        color.a,
+    )
+}
+
+impl Transform for RiemannRectangles {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        for r in &mut self.rectangles {
+            r.transform(transform);
+        }
+        self.accumulated_transform = transform * self.accumulated_transform;
+    }
+}
+
+impl Draw for RiemannRectangles {
+    fn draw(&self, ctx: &mut Context) {
+        for r in &self.rectangles {
+            r.draw(ctx);
+        }
+    }
+}
+
+impl Mobject for RiemannRectangles {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(self.rectangles.iter().map(|r| r.bounding_box()))
+    }
+}
+
+#[test]
+fn test_area_and_riemann_rectangles() {
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let f = |x: GMFloat| 0.25 * x * x;
+    let riemann = RiemannRectangles::new(f, (0.0, 3.0), 6, RiemannSumType::Midpoint);
+    assert!((riemann.sum() - 2.25).abs() < 0.05);
+    scene.add(Box::new(riemann));
+    scene.add(Box::new(Area::between_graphs(
+        |x| x.sin() + 1.0,
+        f,
+        (-3.0, 0.0),
+    )));
+    scene.add(Box::new(FunctionGraph::new(f, (-4.0, 4.0))));
+    scene.save_png(&mut ctx, "riemann_rectangles.png");
+}
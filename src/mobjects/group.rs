@@ -1,9 +1,174 @@
+use std::ops::{Index, IndexMut};
+use std::slice::SliceIndex;
+
+use nalgebra::{Point3, Vector3};
+use tiny_skia::BlendMode;
+
+use crate::math_utils::constants::DEFAULT_MOBJECT_TO_MOBJECT_BUFFER;
 use crate::GMFloat;
 
-use super::{Draw, Mobject};
+use super::{BoundingBox, Draw, Mobject};
 
+#[derive(Clone)]
 pub struct MobjectGroup {
     pub mobjects: Vec<Box<dyn Mobject>>,
+    pub name: Option<String>,
+    /// applied once to the whole group, overlapping children don't show through
+    /// each other
+    pub opacity: GMFloat,
+    /// how the group mixes with what is below it
+    pub blend_mode: BlendMode,
+}
+
+/// options for `MobjectGroup::arrange_in_grid`
+pub struct GridLayout {
+    /// horizontal and vertical gap between cells
+    pub buff: (GMFloat, GMFloat),
+    /// where each mobject sits inside its cell, e.g. ORIGIN centers it, LEFT left aligns it
+    pub cell_alignment: Vector3<GMFloat>,
+    /// fixed height per row, None or a missing entry fits the tallest mobject of the row
+    pub row_heights: Vec<Option<GMFloat>>,
+    /// fixed width per column, None or a missing entry fits the widest mobject of the column
+    pub col_widths: Vec<Option<GMFloat>>,
+    /// fill left to right then top to bottom, otherwise top to bottom first
+    pub fill_rows_first: bool,
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self {
+            buff: (
+                DEFAULT_MOBJECT_TO_MOBJECT_BUFFER,
+                DEFAULT_MOBJECT_TO_MOBJECT_BUFFER,
+            ),
+            cell_alignment: Vector3::zeros(),
+            row_heights: vec![],
+            col_widths: vec![],
+            fill_rows_first: true,
+        }
+    }
+}
+
+impl MobjectGroup {
+    pub fn new(mobjects: Vec<Box<dyn Mobject>>) -> Self {
+        Self {
+            mobjects,
+            name: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    /// line the mobjects up one after another in `direction`, `buff` apart, with their
+    /// `aligned_edge` lined up (ORIGIN centers them); the group keeps its center.
+    /// manim's gap is [`DEFAULT_MOBJECT_TO_MOBJECT_BUFFER`]
+    pub fn arrange(
+        &mut self,
+        direction: Vector3<GMFloat>,
+        buff: GMFloat,
+        aligned_edge: Vector3<GMFloat>,
+    ) {
+        let Some(center) = self.bounding_box().map(|b| b.center()) else {
+            return;
+        };
+        for i in 1..self.mobjects.len() {
+            let (placed, rest) = self.mobjects.split_at_mut(i);
+            let previous = &placed[i - 1];
+            let current = &mut rest[0];
+            let target = previous.get_critical_point(direction + aligned_edge);
+            let movement =
+                target - current.get_critical_point(aligned_edge - direction) + direction * buff;
+            current.move_this(movement);
+        }
+        self.move_to(center);
+    }
+
+    /// put the mobjects into a `rows` x `cols` grid, a missing count is derived from
+    /// the other one or both from the group size; the group keeps its center.
+    /// zero counts are taken as 1, and a grid too small for the group gets more rows
+    /// (or columns when filling columns first)
+    pub fn arrange_in_grid(
+        &mut self,
+        rows: Option<usize>,
+        cols: Option<usize>,
+        layout: &GridLayout,
+    ) {
+        let n = self.mobjects.len();
+        let Some(center) = self.bounding_box().map(|b| b.center()) else {
+            return;
+        };
+        let (rows, cols) = match (rows.map(|r| r.max(1)), cols.map(|c| c.max(1))) {
+            (Some(r), Some(c)) if r * c >= n => (r, c),
+            (Some(_), Some(c)) if layout.fill_rows_first => (n.div_ceil(c), c),
+            (Some(r), _) => (r, n.div_ceil(r)),
+            (None, Some(c)) => (n.div_ceil(c), c),
+            (None, None) => {
+                let c = (n as GMFloat).sqrt().ceil() as usize;
+                (n.div_ceil(c), c)
+            }
+        };
+        let cell_of = |i: usize| {
+            if layout.fill_rows_first {
+                (i / cols, i % cols)
+            } else {
+                (i % rows, i / rows)
+            }
+        };
+
+        let mut heights = vec![0.0; rows];
+        let mut widths = vec![0.0; cols];
+        for (i, m) in self.mobjects.iter().enumerate() {
+            let (r, c) = cell_of(i);
+            heights[r] = m.height().max(heights[r]);
+            widths[c] = m.width().max(widths[c]);
+        }
+        for (r, h) in heights.iter_mut().enumerate() {
+            if let Some(Some(fixed)) = layout.row_heights.get(r) {
+                *h = *fixed;
+            }
+        }
+        for (c, w) in widths.iter_mut().enumerate() {
+            if let Some(Some(fixed)) = layout.col_widths.get(c) {
+                *w = *fixed;
+            }
+        }
+
+        // top left corner of every cell, rows grow downwards
+        let tops: Vec<GMFloat> = heights
+            .iter()
+            .scan(0.0, |y, h| {
+                let top = *y;
+                *y -= h + layout.buff.1;
+                Some(top)
+            })
+            .collect();
+        let lefts: Vec<GMFloat> = widths
+            .iter()
+            .scan(0.0, |x, w| {
+                let left = *x;
+                *x += w + layout.buff.0;
+                Some(left)
+            })
+            .collect();
+
+        for (i, m) in self.mobjects.iter_mut().enumerate() {
+            let (r, c) = cell_of(i);
+            let cell = BoundingBox::new(
+                Point3::new(lefts[c], tops[r] - heights[r], 0.0),
+                Point3::new(lefts[c] + widths[c], tops[r], 0.0),
+            );
+            m.move_to_aligned(
+                cell.critical_point(layout.cell_alignment),
+                layout.cell_alignment,
+            );
+        }
+        self.move_to(center);
+    }
 }
 
 impl super::Transform for MobjectGroup {
@@ -16,10 +181,166 @@ impl super::Transform for MobjectGroup {
 
 impl Draw for MobjectGroup {
     fn draw(&self, ctx: &mut crate::Context) {
+        // a plain group draws straight through, without the cost of a layer
+        let layered = self.opacity < 1.0 || self.blend_mode != BlendMode::SourceOver;
+        if layered {
+            ctx.push_layer();
+        }
         for m in &self.mobjects {
             m.draw(ctx);
         }
+        if layered {
+            ctx.pop_layer(self.opacity, self.blend_mode);
+        }
     }
 }
 
-impl Mobject for MobjectGroup {}
+impl Mobject for MobjectGroup {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(self.mobjects.iter().map(|m| m.bounding_box()))
+    }
+
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        self.mobjects.iter().map(|m| m.as_ref()).collect()
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        self.mobjects
+            .iter_mut()
+            .map(|m| &mut **m as &mut dyn Mobject)
+            .collect()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl<I: SliceIndex<[Box<dyn Mobject>]>> Index<I> for MobjectGroup {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.mobjects[index]
+    }
+}
+
+impl<I: SliceIndex<[Box<dyn Mobject>]>> IndexMut<I> for MobjectGroup {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.mobjects[index]
+    }
+}
+
+#[test]
+fn test_arrange() {
+    use super::{Rectangle, Transform};
+    use crate::math_utils::constants::{DOWN, LEFT, RIGHT, UP};
+    use crate::{Context, Scene};
+
+    let squares = |n: usize| -> Vec<Box<dyn Mobject>> {
+        (0..n)
+            .map(|i| {
+                let mut r = Rectangle::default();
+                r.scale(0.5 + 0.25 * (i % 3) as GMFloat);
+                Box::new(r) as Box<dyn Mobject>
+            })
+            .collect()
+    };
+
+    let mut row = MobjectGroup::new(squares(3));
+    row.arrange(RIGHT, 0.5, DOWN);
+    let m = &row.mobjects;
+    assert!((m[1].get_left().x - m[0].get_right().x - 0.5).abs() < 1e-5);
+    assert!((m[2].get_bottom().y - m[0].get_bottom().y).abs() < 1e-5);
+    row.move_this(Vector3::new(0.0, 3.0, 0.0));
+
+    let mut grid = MobjectGroup::new(squares(7));
+    grid.arrange_in_grid(
+        None,
+        Some(3),
+        &GridLayout {
+            cell_alignment: LEFT,
+            ..Default::default()
+        },
+    );
+    let m = &grid.mobjects;
+    // column 0 holds the 0.5 squares, column 2 the 1.0 squares
+    assert!((m[3].get_left().x - m[0].get_left().x).abs() < 1e-5);
+    assert!((m[1].get_left().x - m[0].get_right().x - 0.25).abs() < 1e-5);
+    assert!(m[3].get_top().y < m[0].get_bottom().y);
+    assert!((m[6].get_center().y - m[0].get_center().y + 2.25).abs() < 1e-5);
+    grid.move_this(UP * -1.0);
+
+    // counts that can't hold the group are grown, zero counts are taken as 1
+    let mut small = MobjectGroup::new(squares(5));
+    small.arrange_in_grid(Some(1), Some(2), &GridLayout::default());
+    let m = &small.mobjects;
+    assert!((m[2].get_center().x - m[0].get_center().x).abs() < 1e-5);
+    assert!(m[4].get_top().y < m[2].get_bottom().y);
+    small.arrange_in_grid(Some(0), None, &GridLayout::default());
+    assert!((small.mobjects[4].get_center().y - small.mobjects[0].get_center().y).abs() < 1e-5);
+    small.arrange_in_grid(None, Some(0), &GridLayout::default());
+    assert!(small.mobjects[4].get_top().y < small.mobjects[0].get_bottom().y);
+
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    scene.add(Box::new(row));
+    scene.add(Box::new(grid));
+    scene.save_png(&mut ctx, "arrange.png");
+}
+
+#[test]
+fn test_group_layers() {
+    use super::{DrawConfig, Rectangle, Transform};
+    use crate::{color, Background, Context, ContextType, Scene};
+
+    let square = |color: crate::Color, x: GMFloat, y: GMFloat| {
+        let mut square = Rectangle {
+            draw_config: DrawConfig::filled(color),
+            ..Default::default()
+        };
+        square.scale(2.0);
+        square.move_this(Vector3::new(x, y, 0.0));
+        Box::new(square) as Box<dyn Mobject>
+    };
+    let mut ctx = Context::default();
+    let mut scene = Scene {
+        background: Some(Background::Color(color::BLACK)),
+        ..Default::default()
+    };
+    // the green square hides the red one, then the pair fades as one
+    let mut faded = MobjectGroup::new(vec![
+        square(color::PURE_RED, -6.0, 0.0),
+        square(color::PURE_GREEN, -5.0, 0.0),
+    ]);
+    faded.opacity = 0.5;
+    scene.add(Box::new(faded));
+    for (i, blend_mode) in [BlendMode::Plus, BlendMode::Multiply, BlendMode::Screen]
+        .into_iter()
+        .enumerate()
+    {
+        let x = -2.0 + 3.0 * i as GMFloat;
+        scene.add(square(color::PURE_BLUE, x, 0.0));
+        let mut group = MobjectGroup::new(vec![square(color::YELLOW, x + 1.0, 1.0)]);
+        group.blend_mode = blend_mode;
+        scene.add(Box::new(group));
+    }
+    scene.save_png(&mut ctx, "group_layers.png");
+
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let rgb_at = |x: GMFloat, y: GMFloat| {
+        let p = pixmap
+            .pixel(
+                ctx.scene_config.convert_coord_x(x) as u32,
+                ctx.scene_config.convert_coord_y(y) as u32,
+            )
+            .unwrap();
+        (p.red(), p.green(), p.blue())
+    };
+    assert_eq!(rgb_at(-4.5, 1.0), (0x00, 0x80, 0x00));
+    // additive, multiply and screen where yellow covers blue
+    assert_eq!(rgb_at(-0.5, 1.5), (0xff, 0xff, 0xff));
+    assert_eq!(rgb_at(2.5, 1.5), (0x00, 0x00, 0x00));
+    assert_eq!(rgb_at(5.5, 1.5), (0xff, 0xff, 0xff));
+}
@@ -0,0 +1,266 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use nalgebra::Point3;
+use tiny_skia::{FilterQuality, Pixmap, PixmapPaint};
+
+use crate::{Context, ContextType, GMFloat, SceneConfig};
+
+use super::{path::polyline_elements, BoundingBox, Draw, Mobject, PathElement, Transform};
+
+/// height in scene units of a freshly loaded image
+pub const DEFAULT_IMAGE_HEIGHT: GMFloat = 2.0;
+
+#[derive(Debug)]
+pub enum ImageError {
+    Io(std::io::Error),
+    /// neither png nor jpeg
+    UnknownFormat,
+    Decode(String),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Io(e) => write!(f, "can't read image file: {}", e),
+            ImageError::UnknownFormat => write!(f, "only png and jpeg images are supported"),
+            ImageError::Decode(message) => write!(f, "can't decode image: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<std::io::Error> for ImageError {
+    fn from(value: std::io::Error) -> Self {
+        ImageError::Io(value)
+    }
+}
+
+/// png or jpeg bytes, told apart by their signature
+pub fn decode_image(data: &[u8]) -> Result<Pixmap, ImageError> {
+    if data.starts_with(b"\x89PNG") {
+        Pixmap::decode_png(data).map_err(|e| ImageError::Decode(e.to_string()))
+    } else if data.starts_with(&[0xff, 0xd8]) {
+        decode_jpeg(data)
+    } else {
+        Err(ImageError::UnknownFormat)
+    }
+}
+
+fn decode_jpeg(data: &[u8]) -> Result<Pixmap, ImageError> {
+    use jpeg_decoder::PixelFormat;
+
+    let mut decoder = jpeg_decoder::Decoder::new(data);
+    let pixels = decoder
+        .decode()
+        .map_err(|e| ImageError::Decode(e.to_string()))?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| ImageError::Decode("missing jpeg header".to_owned()))?;
+    let rgba: Vec<u8> = match info.pixel_format {
+        PixelFormat::L8 => pixels.iter().flat_map(|l| [*l, *l, *l, 0xff]).collect(),
+        // big endian, the high byte is close enough
+        PixelFormat::L16 => pixels
+            .chunks_exact(2)
+            .flat_map(|l| [l[0], l[0], l[0], 0xff])
+            .collect(),
+        PixelFormat::RGB24 => pixels
+            .chunks_exact(3)
+            .flat_map(|c| [c[0], c[1], c[2], 0xff])
+            .collect(),
+        // adobe jpegs store inverted cmyk
+        PixelFormat::CMYK32 => pixels
+            .chunks_exact(4)
+            .flat_map(|c| {
+                let k = c[3] as u16;
+                let channel = |v: u8| (v as u16 * k / 255) as u8;
+                [channel(c[0]), channel(c[1]), channel(c[2]), 0xff]
+            })
+            .collect(),
+    };
+    let size = tiny_skia::IntSize::from_wh(info.width as u32, info.height as u32)
+        .ok_or_else(|| ImageError::Decode("empty jpeg".to_owned()))?;
+    Pixmap::from_vec(rgba, size).ok_or_else(|| ImageError::Decode("bad jpeg size".to_owned()))
+}
+
+/// a raster image placed by three of its corners, so any affine transform
+/// including rotation and shear keeps it exact
+#[derive(Debug, Clone)]
+pub struct ImageMobject {
+    /// shared between copies, the pixels never change
+    pub pixmap: Arc<Pixmap>,
+    pub lower_left: Point3<GMFloat>,
+    pub lower_right: Point3<GMFloat>,
+    pub upper_left: Point3<GMFloat>,
+    pub opacity: GMFloat,
+    /// `Nearest` keeps pixel art crisp, `Bilinear` smooths photos
+    pub filter_quality: FilterQuality,
+}
+
+impl ImageMobject {
+    /// centered at origin, [`DEFAULT_IMAGE_HEIGHT`] high
+    pub fn new(pixmap: Pixmap) -> Self {
+        let height = DEFAULT_IMAGE_HEIGHT;
+        let width = height * pixmap.width() as GMFloat / pixmap.height() as GMFloat;
+        Self {
+            pixmap: Arc::new(pixmap),
+            lower_left: Point3::new(-width / 2.0, -height / 2.0, 0.0),
+            lower_right: Point3::new(width / 2.0, -height / 2.0, 0.0),
+            upper_left: Point3::new(-width / 2.0, height / 2.0, 0.0),
+            opacity: 1.0,
+            filter_quality: FilterQuality::Bilinear,
+        }
+    }
+
+    /// local png or jpeg file
+    pub fn open(file_path: &str) -> Result<Self, ImageError> {
+        let data = std::fs::read(file_path)?;
+        Ok(Self::new(decode_image(&data)?))
+    }
+
+    pub fn upper_right(&self) -> Point3<GMFloat> {
+        self.upper_left + (self.lower_right - self.lower_left)
+    }
+
+    /// image pixels to output pixels
+    fn pixel_transform(&self, scene_config: &SceneConfig) -> tiny_skia::Transform {
+        let pixel = |p: Point3<GMFloat>| {
+            (
+                scene_config.convert_coord_x(p.x),
+                scene_config.convert_coord_y(p.y),
+            )
+        };
+        let (ulx, uly) = pixel(self.upper_left);
+        let (llx, lly) = pixel(self.lower_left);
+        let (lrx, lry) = pixel(self.lower_right);
+        let (w, h) = (self.pixmap.width() as f32, self.pixmap.height() as f32);
+        tiny_skia::Transform::from_row(
+            (lrx - llx) / w,
+            (lry - lly) / w,
+            (llx - ulx) / h,
+            (lly - uly) / h,
+            ulx,
+            uly,
+        )
+    }
+}
+
+impl Transform for ImageMobject {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.lower_left = transform * self.lower_left;
+        self.lower_right = transform * self.lower_right;
+        self.upper_left = transform * self.upper_left;
+    }
+}
+
+impl Draw for ImageMobject {
+    fn draw(&self, ctx: &mut Context) {
+        let transform = self.pixel_transform(&ctx.scene_config);
+        if let ContextType::TinySKIA(pixmap) = &mut ctx.ctx_type {
+            let paint = PixmapPaint {
+                opacity: self.opacity.clamp(0.0, 1.0),
+                quality: self.filter_quality,
+                ..Default::default()
+            };
+            pixmap.draw_pixmap(
+                0,
+                0,
+                self.pixmap.as_ref().as_ref(),
+                &paint,
+                transform,
+                ctx.mask.as_ref(),
+            );
+        }
+    }
+}
+
+impl Mobject for ImageMobject {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.control_points())
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        vec![
+            self.lower_left,
+            self.lower_right,
+            self.upper_right(),
+            self.upper_left,
+        ]
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        if let [lower_left, lower_right, _, upper_left] = points {
+            (self.lower_left, self.lower_right, self.upper_left) =
+                (*lower_left, *lower_right, *upper_left);
+        }
+    }
+
+    /// the outline of the image
+    fn path_elements(&self) -> Vec<PathElement> {
+        polyline_elements(&self.control_points(), true)
+    }
+}
+
+#[test]
+fn test_image() {
+    use crate::Scene;
+
+    // 2x2 checker board
+    let mut pixmap = Pixmap::new(2, 2).unwrap();
+    pixmap.fill(tiny_skia::Color::WHITE);
+    let red = tiny_skia::PremultipliedColorU8::from_rgba(0xff, 0, 0, 0xff).unwrap();
+    pixmap.pixels_mut()[0] = red;
+    pixmap.pixels_mut()[3] = red;
+    pixmap.save_png("checker.png").unwrap();
+
+    let mut checker = ImageMobject::open("checker.png").unwrap();
+    checker.filter_quality = FilterQuality::Nearest;
+    checker.scale(2.0);
+    let b = checker.bounding_box().unwrap();
+    assert_eq!(b.width(), 4.0);
+    assert_eq!(b.height(), 4.0);
+    assert!(ImageMobject::open("Cargo.toml").is_err());
+
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let mut sheared = checker.clone();
+    sheared.transform(nalgebra::Transform3::from_matrix_unchecked(
+        nalgebra::Matrix4::new(
+            1.0, 0.5, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ),
+    ));
+    sheared.move_this(nalgebra::Vector3::new(-4.0, 0.0, 0.0));
+    checker.transform(nalgebra::Transform3::from_matrix_unchecked(
+        nalgebra::Matrix4::new_rotation(nalgebra::Vector3::new(0.0, 0.0, 0.5)),
+    ));
+    checker.move_this(nalgebra::Vector3::new(3.0, 0.0, 0.0));
+    checker.opacity = 0.5;
+    scene.add(Box::new(sheared));
+    scene.add(Box::new(checker));
+
+    // a wide box fits the image by its height, centered
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="40" height="20">
+        <image x="0" y="0" width="40" height="20" xlink:href="checker.png" image-rendering="optimizeSpeed"/>
+    </svg>"##;
+    std::fs::write("image.svg", svg).unwrap();
+    let mut shape = super::svg_shape::open_svg_file("image.svg");
+    let b = shape.bounding_box().unwrap();
+    assert!((b.min.x - 1.0).abs() < 1e-5 && (b.max.x - 3.0).abs() < 1e-5);
+    assert!((b.min.y + 2.0).abs() < 1e-5 && b.max.y.abs() < 1e-5);
+    shape.move_this(nalgebra::Vector3::new(-2.0, -2.0, 0.0));
+    scene.add(Box::new(shape));
+    scene.save_png(&mut ctx, "image.png");
+
+    // the upper left pixel is red and sits at the upper left corner
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let x = ctx.scene_config.convert_coord_x(-4.0 - 2.0 + 1.0 + 0.5) as u32;
+    let y = ctx.scene_config.convert_coord_y(1.5) as u32;
+    let p = pixmap.pixel(x, y).unwrap();
+    assert_eq!((p.red(), p.green(), p.blue()), (0xff, 0, 0));
+}
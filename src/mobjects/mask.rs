@@ -0,0 +1,197 @@
+use tiny_skia::{Mask, MaskType};
+
+use crate::{Context, ContextType, GMFloat};
+
+use super::{path::to_pixel_path, BoundingBox, Draw, Mobject, Transform};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MaskKind {
+    /// the filled outline of the mask, its colors and opacity don't matter
+    #[default]
+    Clip,
+    /// the mask as drawn, where it is opaque the content shows
+    Alpha,
+    /// the brightness of the mask as drawn, white shows the content and black hides it
+    Luminance,
+}
+
+/// `content` drawn only where `mask` lets it through, e.g. an image seen through
+/// text or a wipe with a moving rectangle; the mask itself is never drawn
+///
+/// transforms move both, change `mask` alone to animate the reveal
+#[derive(Clone)]
+pub struct Masked {
+    pub content: Box<dyn Mobject>,
+    pub mask: Box<dyn Mobject>,
+    pub kind: MaskKind,
+    /// show the content outside the mask instead
+    pub invert: bool,
+}
+
+impl Masked {
+    pub fn new(content: Box<dyn Mobject>, mask: Box<dyn Mobject>, kind: MaskKind) -> Self {
+        Self {
+            content,
+            mask,
+            kind,
+            invert: false,
+        }
+    }
+
+    /// coverage of the mask in output pixels, None without a tiny-skia context
+    fn build_mask(&self, ctx: &Context) -> Option<Mask> {
+        let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+            return None;
+        };
+        let mut mask = match self.kind {
+            MaskKind::Clip => {
+                let mut mask = Mask::new(pixmap.width(), pixmap.height())?;
+                let fill_rule = self
+                    .mask
+                    .draw_config()
+                    .map(|d| d.fill_rule)
+                    .unwrap_or_default();
+                if let Some(path) = to_pixel_path(&self.mask.path_elements(), &ctx.scene_config) {
+                    mask.fill_path(&path, fill_rule, true, tiny_skia::Transform::identity());
+                }
+                mask
+            }
+            MaskKind::Alpha | MaskKind::Luminance => {
+                let mut offscreen = ctx.offscreen();
+                self.mask.draw(&mut offscreen);
+                let ContextType::TinySKIA(drawn) = &offscreen.ctx_type else {
+                    return None;
+                };
+                let mask_type = match self.kind {
+                    MaskKind::Alpha => MaskType::Alpha,
+                    _ => MaskType::Luminance,
+                };
+                Mask::from_pixmap(drawn.as_ref(), mask_type)
+            }
+        };
+        if self.invert {
+            mask.invert();
+        }
+        Some(mask)
+    }
+}
+
+/// coverage of both masks, for masks inside masks
+fn intersect(mut mask: Mask, other: &Mask) -> Mask {
+    for (a, b) in mask.data_mut().iter_mut().zip(other.data()) {
+        *a = (*a as u16 * *b as u16 / 255) as u8;
+    }
+    mask
+}
+
+impl Transform for Masked {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.content.transform(transform);
+        self.mask.transform(transform);
+    }
+}
+
+impl Draw for Masked {
+    fn draw(&self, ctx: &mut Context) {
+        let Some(mask) = self.build_mask(ctx) else {
+            self.content.draw(ctx);
+            return;
+        };
+        let outer = ctx.mask.take();
+        ctx.mask = Some(match &outer {
+            Some(outer) => intersect(mask, outer),
+            None => mask,
+        });
+        self.content.draw(ctx);
+        ctx.mask = outer;
+    }
+}
+
+impl Mobject for Masked {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.content.bounding_box()
+    }
+
+    /// only the content, the mask is not part of the picture
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        vec![self.content.as_ref()]
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        vec![self.content.as_mut()]
+    }
+}
+
+#[test]
+fn test_masks() {
+    use super::gradient::{Gradient, GradientStop};
+    use super::{Arc, DrawConfig, Rectangle};
+    use crate::{color, math_utils::constants::PI, Scene};
+    use nalgebra::{Point3, Vector3};
+
+    let red_square = |x: GMFloat| {
+        let mut square = Rectangle {
+            draw_config: DrawConfig::filled(color::PURE_RED),
+            ..Default::default()
+        };
+        square.scale(4.0);
+        square.move_this(Vector3::new(x - 2.0, -2.0, 0.0));
+        Box::new(square)
+    };
+    let circle = |x: GMFloat| Box::new(Arc::new(Point3::new(x, 0.0, 0.0), 0.0, 2.0 * PI, 1.5));
+
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    // square through a circle, square around a circle hole
+    scene.add(Box::new(Masked::new(
+        red_square(-5.0),
+        circle(-5.0),
+        MaskKind::Clip,
+    )));
+    let mut hole = Masked::new(red_square(0.0), circle(0.0), MaskKind::Clip);
+    hole.invert = true;
+    scene.add(Box::new(hole));
+    // soft wipe from left to right
+    let mut wipe = Rectangle {
+        draw_config: DrawConfig::default().with_fill_gradient(Gradient::linear(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            vec![
+                GradientStop::new(0.0, color::WHITE),
+                GradientStop::new(1.0, color::WHITE.with_alpha(0)),
+            ],
+        )),
+        ..Default::default()
+    };
+    wipe.draw_config.stroke_width = 0.0;
+    wipe.scale(4.0);
+    wipe.move_this(Vector3::new(3.0, -2.0, 0.0));
+    scene.add(Box::new(Masked::new(
+        red_square(5.0),
+        Box::new(wipe),
+        MaskKind::Alpha,
+    )));
+    scene.save_png(&mut ctx, "masks.png");
+
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let red_at = |x: GMFloat, y: GMFloat| {
+        let p = pixmap
+            .pixel(
+                ctx.scene_config.convert_coord_x(x) as u32,
+                ctx.scene_config.convert_coord_y(y) as u32,
+            )
+            .unwrap();
+        p.red()
+    };
+    assert_eq!(red_at(-5.0, 0.0), 0xff);
+    assert_eq!(red_at(-6.9, 1.9), 0);
+    assert_eq!(red_at(0.0, 0.0), 0);
+    assert_eq!(red_at(-1.9, 1.9), 0xff);
+    // the wipe fades out across the square
+    assert!(red_at(3.1, 0.0) > 0xf0);
+    assert!(red_at(5.0, 0.0) > 0x60 && red_at(5.0, 0.0) < 0xa0);
+    assert!(red_at(6.9, 0.0) < 0x10);
+    assert!(ctx.mask.is_none());
+}
@@ -1,24 +1,284 @@
-pub trait Mobject: Transform + Draw {}
-pub trait MobjectClone: Mobject {
-    fn mobject_clone(&self) -> Box<dyn MobjectClone>;
+pub trait Mobject: Transform + Draw + AsAny + MobjectClone {
+    /// None when there is nothing to draw
+    fn bounding_box(&self) -> Option<BoundingBox>;
+
+    fn get_center(&self) -> Point3<GMFloat> {
+        self.get_critical_point(Vector3::zeros())
+    }
+    /// point of the bounding box in `direction` from its center, see [`BoundingBox::critical_point`]
+    fn get_critical_point(&self, direction: Vector3<GMFloat>) -> Point3<GMFloat> {
+        self.bounding_box()
+            .map(|b| b.critical_point(direction))
+            .unwrap_or(Point3::origin())
+    }
+    fn get_top(&self) -> Point3<GMFloat> {
+        self.get_critical_point(UP)
+    }
+    fn get_bottom(&self) -> Point3<GMFloat> {
+        self.get_critical_point(DOWN)
+    }
+    fn get_left(&self) -> Point3<GMFloat> {
+        self.get_critical_point(LEFT)
+    }
+    fn get_right(&self) -> Point3<GMFloat> {
+        self.get_critical_point(RIGHT)
+    }
+    /// e.g. `get_corner(UL)` for the upper left corner
+    fn get_corner(&self, direction: Vector3<GMFloat>) -> Point3<GMFloat> {
+        self.get_critical_point(direction)
+    }
+    fn width(&self) -> GMFloat {
+        self.bounding_box().map(|b| b.width()).unwrap_or(0.0)
+    }
+    fn height(&self) -> GMFloat {
+        self.bounding_box().map(|b| b.height()).unwrap_or(0.0)
+    }
+    fn depth(&self) -> GMFloat {
+        self.bounding_box().map(|b| b.depth()).unwrap_or(0.0)
+    }
+
+    /// points the shape is built from, in a fixed order, so two states of a mobject
+    /// can be interpolated; collected from the submobjects unless overridden
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        self.submobjects()
+            .into_iter()
+            .flat_map(|s| s.control_points())
+            .collect()
+    }
+    /// counterpart of `control_points`, expects as many points as it returns
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        distribute_control_points(self.submobjects_mut(), points);
+    }
+
+    /// outline in scene coordinates, collected from the submobjects unless overridden
+    fn path_elements(&self) -> Vec<PathElement> {
+        self.submobjects()
+            .into_iter()
+            .flat_map(|s| s.path_elements())
+            .collect()
+    }
+
+    /// fill and stroke style, None for mobjects styled through their submobjects
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        None
+    }
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        None
+    }
+
+    /// direct children, empty for leaf mobjects
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        vec![]
+    }
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        vec![]
+    }
+    /// name to look the mobject up by, e.g. the id of an svg element
+    fn name(&self) -> Option<&str> {
+        None
+    }
+    fn submobject(&self, index: usize) -> Option<&dyn Mobject> {
+        self.submobjects().into_iter().nth(index)
+    }
+    fn submobject_mut(&mut self, index: usize) -> Option<&mut dyn Mobject> {
+        self.submobjects_mut().into_iter().nth(index)
+    }
+    /// children in `range`, cut short at the last child
+    fn submobject_range(&self, range: Range<usize>) -> Vec<&dyn Mobject> {
+        let len = range.len();
+        self.submobjects()
+            .into_iter()
+            .skip(range.start)
+            .take(len)
+            .collect()
+    }
+    fn submobject_range_mut(&mut self, range: Range<usize>) -> Vec<&mut dyn Mobject> {
+        let len = range.len();
+        self.submobjects_mut()
+            .into_iter()
+            .skip(range.start)
+            .take(len)
+            .collect()
+    }
+    /// every mobject below this one, depth first with parents before their children
+    fn descendants(&self) -> Vec<&dyn Mobject> {
+        let mut family = vec![];
+        for s in self.submobjects() {
+            family.push(s);
+            family.extend(s.descendants());
+        }
+        family
+    }
+    /// call `f` on every mobject below this one, in `descendants` order
+    fn for_each_descendant_mut(&mut self, f: &mut dyn FnMut(&mut dyn Mobject)) {
+        for s in self.submobjects_mut() {
+            f(&mut *s);
+            s.for_each_descendant_mut(f);
+        }
+    }
+    /// first descendant called `name`, in `descendants` order
+    fn find_by_name(&self, name: &str) -> Option<&dyn Mobject> {
+        self.descendants()
+            .into_iter()
+            .find(|m| m.name() == Some(name))
+    }
+    fn find_by_name_mut(&mut self, name: &str) -> Option<&mut dyn Mobject> {
+        for s in self.submobjects_mut() {
+            if s.name() == Some(name) {
+                return Some(s);
+            }
+            if let Some(found) = s.find_by_name_mut(name) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    /// move so that the center lands on `point`
+    fn move_to(&mut self, point: Point3<GMFloat>) {
+        self.move_to_aligned(point, Vector3::zeros());
+    }
+    /// move so that the critical point in `aligned_edge` lands on `point`
+    fn move_to_aligned(&mut self, point: Point3<GMFloat>, aligned_edge: Vector3<GMFloat>) {
+        let movement = point - self.get_critical_point(aligned_edge);
+        self.move_this(movement);
+    }
+    /// place beside `other` in `direction`, e.g. RIGHT puts this to the right of other,
+    /// `buff` apart and vertically centered with it; manim's gap is
+    /// [`DEFAULT_MOBJECT_TO_MOBJECT_BUFFER`](crate::math_utils::constants::DEFAULT_MOBJECT_TO_MOBJECT_BUFFER)
+    fn next_to(&mut self, other: &dyn Mobject, direction: Vector3<GMFloat>, buff: GMFloat) {
+        self.next_to_point(other.get_critical_point(direction), direction, buff);
+    }
+    fn next_to_point(
+        &mut self,
+        point: Point3<GMFloat>,
+        direction: Vector3<GMFloat>,
+        buff: GMFloat,
+    ) {
+        let movement = point - self.get_critical_point(-direction) + direction * buff;
+        self.move_this(movement);
+    }
+    /// line up the edge in `direction` with the same edge of `other`, e.g. LEFT aligns
+    /// left edges and leaves the vertical position alone
+    fn align_to(&mut self, other: &dyn Mobject, direction: Vector3<GMFloat>) {
+        self.align_to_point(other.get_critical_point(direction), direction);
+    }
+    fn align_to_point(&mut self, point: Point3<GMFloat>, direction: Vector3<GMFloat>) {
+        let movement =
+            (point - self.get_critical_point(direction)).component_mul(&axis_mask(direction));
+        self.move_this(movement);
+    }
+    /// push against the frame border in `edge`, keeping `buff` from it; manim's gap is
+    /// [`DEFAULT_MOBJECT_TO_EDGE_BUFFER`](crate::math_utils::constants::DEFAULT_MOBJECT_TO_EDGE_BUFFER)
+    fn to_edge(&mut self, edge: Vector3<GMFloat>, buff: GMFloat, scene_config: &SceneConfig) {
+        let target = scene_config.frame_bounding_box().critical_point(edge);
+        let movement =
+            (target - self.get_critical_point(edge) - edge * buff).component_mul(&axis_mask(edge));
+        self.move_this(movement);
+    }
+    /// e.g. `to_corner(UL, ..)` for the upper left corner of the frame
+    fn to_corner(&mut self, corner: Vector3<GMFloat>, buff: GMFloat, scene_config: &SceneConfig) {
+        self.to_edge(corner, buff, scene_config);
+    }
 }
 
+/// 1 for each axis `direction` points along, 0 for the others
+fn axis_mask(direction: Vector3<GMFloat>) -> Vector3<GMFloat> {
+    direction.map(|d| if d != 0.0 { 1.0 } else { 0.0 })
+}
+/// lets a `dyn Mobject` be downcast to its concrete type, see [`arena::MobjectArena::get`]
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// hand `points` out to `mobjects` in order, each takes as many as it has, returns the rest
+pub(crate) fn distribute_control_points<'a>(
+    mobjects: Vec<&mut dyn Mobject>,
+    points: &'a [Point3<GMFloat>],
+) -> &'a [Point3<GMFloat>] {
+    let mut rest = points;
+    for m in mobjects {
+        let count = m.control_points().len().min(rest.len());
+        m.set_control_points(&rest[..count]);
+        rest = &rest[count..];
+    }
+    rest
+}
+
+/// deep copies of mobjects behind `dyn Mobject`, implemented for every `Mobject + Clone`
+pub trait MobjectClone {
+    fn mobject_clone(&self) -> Box<dyn Mobject>;
+    /// turn into a copy of `other`, false and unchanged when other is of another type,
+    /// use `Scene::become_mobject` to swap in any type
+    fn become_mobject(&mut self, other: &dyn Mobject) -> bool;
+}
+
+impl<T: Mobject + Clone + 'static> MobjectClone for T {
+    fn mobject_clone(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+    fn become_mobject(&mut self, other: &dyn Mobject) -> bool {
+        match other.as_any().downcast_ref::<T>() {
+            Some(other) => {
+                *self = other.clone();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Clone for Box<dyn Mobject> {
+    fn clone(&self) -> Self {
+        (**self).mobject_clone()
+    }
+}
+
+use std::any::Any;
 use std::f32::consts::PI;
+use std::ops::Range;
 
 use crate::{
-    math_utils::k_for_bezier_arc, Color, Context, ContextType, GMFloat, Scene, SceneConfig,
+    math_utils::{
+        constants::{DOWN, LEFT, RIGHT, UP},
+        k_for_bezier_arc,
+    },
+    Color, Context, ContextType, GMFloat, Scene, SceneConfig,
 };
 
 use nalgebra::{point, Point, Point2, Point3, Vector2, Vector3};
-use tiny_skia::{LineCap, LineJoin, Paint, Stroke, StrokeDash};
+use tiny_skia::{FillRule, LineCap, LineJoin, Paint, Stroke, StrokeDash};
+pub mod annotation;
+pub mod arena;
+pub mod boolean;
+pub mod chart;
+pub mod effects;
 pub mod formula;
+pub mod gradient;
+pub mod graph;
 pub mod group;
+pub mod image;
+pub mod mask;
 pub mod path;
 pub mod svg_shape;
+pub mod table;
 pub mod text;
 pub mod three_d_viewport;
 pub mod polygon;
 
+use gradient::Gradient;
+use path::{polyline_elements, to_pixel_path, PathElement};
+
 pub trait Transform {
     fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>);
     fn scale(&mut self, scale_factor: GMFloat) {
@@ -36,23 +296,273 @@ pub trait Draw {
     fn draw(&self, ctx: &mut Context);
 }
 
-#[derive(Debug, Clone, Copy)]
+/// axis aligned box around a mobject, in scene coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point3<GMFloat>,
+    pub max: Point3<GMFloat>,
+}
+
+impl BoundingBox {
+    pub fn new(min: Point3<GMFloat>, max: Point3<GMFloat>) -> Self {
+        Self { min, max }
+    }
+
+    /// None when there are no points
+    pub fn from_points<I: IntoIterator<Item = Point3<GMFloat>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |b, p| {
+            Self::new(b.min.inf(&p), b.max.sup(&p))
+        }))
+    }
+
+    pub fn union(&self, other: &BoundingBox) -> Self {
+        Self::new(self.min.inf(&other.min), self.max.sup(&other.max))
+    }
+
+    /// box around all given boxes, None entries are skipped
+    pub fn union_all<I: IntoIterator<Item = Option<BoundingBox>>>(boxes: I) -> Option<Self> {
+        boxes
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| a.union(&b))
+    }
+
+    pub fn center(&self) -> Point3<GMFloat> {
+        nalgebra::center(&self.min, &self.max)
+    }
+
+    pub fn width(&self) -> GMFloat {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> GMFloat {
+        self.max.y - self.min.y
+    }
+
+    pub fn depth(&self) -> GMFloat {
+        self.max.z - self.min.z
+    }
+
+    /// point of the box in `direction` from the center, e.g. UP is the top center
+    /// and UP + LEFT the upper left corner
+    pub fn critical_point(&self, direction: Vector3<GMFloat>) -> Point3<GMFloat> {
+        let center = self.center();
+        let half = (self.max - self.min) / 2.0;
+        let sign = |d: GMFloat| {
+            if d > 0.0 {
+                1.0
+            } else if d < 0.0 {
+                -1.0
+            } else {
+                0.0
+            }
+        };
+        center + direction.map(sign).component_mul(&half)
+    }
+
+    pub fn grow(&self, buff: GMFloat) -> Self {
+        let buff = Vector3::new(buff, buff, 0.0);
+        Self::new(self.min - buff, self.max + buff)
+    }
+}
+
+/// how a shape is filled and stroked, the fill is painted first and the stroke on top
+#[derive(Debug, Clone, PartialEq)]
 pub struct DrawConfig {
-    stoke_width: GMFloat,
-    fill: bool,
-    color: Color,
+    /// in scene units, 0 draws no stroke
+    pub stroke_width: GMFloat,
+    pub stroke_color: Color,
+    /// multiplied with the alpha of `stroke_color`
+    pub stroke_opacity: GMFloat,
+    pub fill_color: Color,
+    /// multiplied with the alpha of `fill_color`, 0 draws no fill
+    pub fill_opacity: GMFloat,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub miter_limit: GMFloat,
+    pub fill_rule: FillRule,
+    /// alternating dash and gap lengths in scene units, empty for a solid stroke;
+    /// an odd count is repeated once, like svg does
+    pub dash_pattern: Vec<GMFloat>,
+    /// how far into the pattern the stroke starts, animate it for marching ants
+    pub dash_offset: GMFloat,
+    /// paints the fill instead of `fill_color` when set
+    pub fill_gradient: Option<Gradient>,
+    /// paints the stroke instead of `stroke_color` when set
+    pub stroke_gradient: Option<Gradient>,
 }
 
 impl Default for DrawConfig {
     fn default() -> Self {
+        let theme = crate::theme::theme();
         DrawConfig {
-            stoke_width: 0.25,
-            fill: true,
-            color: Default::default(),
+            stroke_width: theme.stroke_width,
+            stroke_color: theme.primary,
+            stroke_opacity: 1.0,
+            fill_color: theme.primary,
+            fill_opacity: 0.0,
+            line_cap: LineCap::Round,
+            line_join: LineJoin::Round,
+            miter_limit: 4.0,
+            fill_rule: FillRule::Winding,
+            dash_pattern: vec![],
+            dash_offset: 0.0,
+            fill_gradient: None,
+            stroke_gradient: None,
+        }
+    }
+}
+
+impl DrawConfig {
+    /// only a stroke
+    pub fn stroked(color: Color, width: GMFloat) -> Self {
+        Self::default().with_stroke(color, width)
+    }
+
+    /// only a fill
+    pub fn filled(color: Color) -> Self {
+        Self {
+            stroke_width: 0.0,
+            ..Self::default()
+        }
+        .with_fill(color, 1.0)
+    }
+
+    /// glyphs filled with the theme foreground color, the usual style for `Text::new`
+    pub fn text_default() -> Self {
+        Self::filled(crate::theme::theme().foreground)
+    }
+
+    pub fn with_stroke(mut self, color: Color, width: GMFloat) -> Self {
+        self.stroke_color = color;
+        self.stroke_width = width;
+        self
+    }
+
+    pub fn with_fill(mut self, color: Color, opacity: GMFloat) -> Self {
+        self.fill_color = color;
+        self.fill_opacity = opacity;
+        self
+    }
+
+    pub fn with_dash(mut self, pattern: &[GMFloat], offset: GMFloat) -> Self {
+        self.dash_pattern = pattern.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+
+    /// fill with `gradient`, `fill_opacity` still applies
+    pub fn with_fill_gradient(mut self, gradient: Gradient) -> Self {
+        self.fill_gradient = Some(gradient);
+        if self.fill_opacity <= 0.0 {
+            self.fill_opacity = 1.0;
+        }
+        self
+    }
+
+    pub fn with_stroke_gradient(mut self, gradient: Gradient) -> Self {
+        self.stroke_gradient = Some(gradient);
+        self
+    }
+
+    /// let the gradients follow a transform of their mobject, shapes call this from
+    /// their `Transform` impl
+    pub fn transform_gradients(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        for g in [&mut self.fill_gradient, &mut self.stroke_gradient]
+            .into_iter()
+            .flatten()
+        {
+            g.transform = transform * g.transform;
+        }
+    }
+
+    /// None for a solid stroke or a pattern tiny-skia can't draw, e.g. all gaps
+    fn stroke_dash(&self, scale_factor: GMFloat) -> Option<StrokeDash> {
+        let mut pattern: Vec<f32> = self.dash_pattern.iter().map(|d| d * scale_factor).collect();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_within(..);
+        }
+        StrokeDash::new(pattern, self.dash_offset * scale_factor)
+    }
+
+    fn has_stroke(&self) -> bool {
+        self.stroke_width > 0.0 && self.stroke_opacity > 0.0
+    }
+
+    fn has_fill(&self) -> bool {
+        self.fill_opacity > 0.0
+    }
+
+    /// fill and stroke `path`, given in pixel coordinates, with this style
+    pub(crate) fn render_path(&self, ctx: &mut Context, path: &tiny_skia::Path) {
+        let scale_factor = ctx.scene_config.scale_factor;
+        match &mut ctx.ctx_type {
+            ContextType::TinySKIA(pixmap) => {
+                let mut paint = Paint::default();
+                paint.anti_alias = true;
+                if self.has_fill() {
+                    paint.shader = shader(
+                        self.fill_color,
+                        self.fill_gradient.as_ref(),
+                        self.fill_opacity,
+                        &ctx.scene_config,
+                    );
+                    pixmap.fill_path(
+                        path,
+                        &paint,
+                        self.fill_rule,
+                        tiny_skia::Transform::identity(),
+                        ctx.mask.as_ref(),
+                    );
+                }
+                if self.has_stroke() {
+                    // tiny-skia treats a zero width as a hairline, so it is skipped above
+                    let stroke = Stroke {
+                        width: self.stroke_width * scale_factor,
+                        miter_limit: self.miter_limit,
+                        line_cap: self.line_cap,
+                        line_join: self.line_join,
+                        dash: self.stroke_dash(scale_factor),
+                    };
+                    paint.shader = shader(
+                        self.stroke_color,
+                        self.stroke_gradient.as_ref(),
+                        self.stroke_opacity,
+                        &ctx.scene_config,
+                    );
+                    pixmap.stroke_path(
+                        path,
+                        &paint,
+                        &stroke,
+                        tiny_skia::Transform::identity(),
+                        ctx.mask.as_ref(),
+                    );
+                }
+            }
+            _ => {}
         }
     }
 }
 
+/// the gradient if there is one that can be drawn, the plain color otherwise
+fn shader(
+    color: Color,
+    gradient: Option<&Gradient>,
+    opacity: GMFloat,
+    scene_config: &SceneConfig,
+) -> tiny_skia::Shader<'static> {
+    gradient
+        .and_then(|g| g.shader(opacity, scene_config))
+        .unwrap_or_else(|| {
+            let mut color: tiny_skia::Color = color.into();
+            color.apply_opacity(opacity.clamp(0.0, 1.0));
+            tiny_skia::Shader::SolidColor(color)
+        })
+}
+
+#[derive(Clone)]
 pub struct Rectangle {
     pub p0: Point3<GMFloat>,
     pub p1: Point3<GMFloat>,
@@ -79,61 +589,47 @@ impl Transform for Rectangle {
         self.p1 = transform * self.p1;
         self.p2 = transform * self.p2;
         self.p3 = transform * self.p3;
+        self.draw_config.transform_gradients(transform);
     }
 }
 
 impl Draw for Rectangle {
     fn draw(self: &Self, ctx: &mut Context) {
-        match &mut ctx.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                let scale_factor = ctx.scene_config.scale_factor;
-                let mut pb = tiny_skia::PathBuilder::new();
-                let p0 = (
-                    coordinate_change_x(self.p0[(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.p0[(1)], ctx.scene_config.height) * scale_factor,
-                );
-                let p1 = (
-                    coordinate_change_x(self.p1[(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.p1[(1)], ctx.scene_config.height) * scale_factor,
-                );
-                let p2 = (
-                    coordinate_change_x(self.p2[(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.p2[(1)], ctx.scene_config.height) * scale_factor,
-                );
-                let p3 = (
-                    coordinate_change_x(self.p3[(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.p3[(1)], ctx.scene_config.height) * scale_factor,
-                );
-                pb.move_to(p0.0 as f32, p0.1 as f32);
-                pb.line_to(p1.0 as f32, p1.1 as f32);
-                pb.line_to(p2.0 as f32, p2.1 as f32);
-                pb.line_to(p3.0 as f32, p3.1 as f32);
-                pb.line_to(p0.0 as f32, p0.1 as f32);
-                let path = pb.finish().unwrap();
-
-                let mut stroke = Stroke::default();
-                stroke.width = self.draw_config.stoke_width * scale_factor;
-                stroke.line_cap = LineCap::Round;
-                stroke.line_join = LineJoin::Round;
-                let mut paint = Paint::default();
-                paint.set_color(self.draw_config.color.into());
-
-                paint.anti_alias = true;
-                pixmap.stroke_path(
-                    &path,
-                    &paint,
-                    &stroke,
-                    tiny_skia::Transform::identity(),
-                    None,
-                );
-            }
-            _ => {}
+        if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
         }
     }
 }
 
-impl Mobject for Rectangle {}
+impl Mobject for Rectangle {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points([self.p0, self.p1, self.p2, self.p3])
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        vec![self.p0, self.p1, self.p2, self.p3]
+    }
 
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        if let [p0, p1, p2, p3] = points {
+            (self.p0, self.p1, self.p2, self.p3) = (*p0, *p1, *p2, *p3);
+        }
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        polyline_elements(&[self.p0, self.p1, self.p2, self.p3], true)
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
+
+#[derive(Clone)]
 pub struct SimpleLine {
     pub p0: Point3<GMFloat>,
     pub p1: Point3<GMFloat>,
@@ -154,49 +650,47 @@ impl Transform for SimpleLine {
     fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
         self.p0 = transform * self.p0;
         self.p1 = transform * self.p1;
+        self.draw_config.transform_gradients(transform);
     }
 }
 
 impl Draw for SimpleLine {
     fn draw(self: &Self, ctx: &mut Context) {
-        let scale_factor = ctx.scene_config.scale_factor;
-        match &mut ctx.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                let mut pb = tiny_skia::PathBuilder::new();
-                let p0 = (
-                    coordinate_change_x(self.p0[(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.p0[(1)], ctx.scene_config.height) * scale_factor,
-                );
-                let p1 = (
-                    coordinate_change_x(self.p1[(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.p1[(1)], ctx.scene_config.height) * scale_factor,
-                );
-                pb.move_to(p0.0 as f32, p0.1 as f32);
-                pb.line_to(p1.0 as f32, p1.1 as f32);
-                let path = pb.finish().unwrap();
-
-                let mut stroke = Stroke::default();
-                stroke.width = self.draw_config.stoke_width * scale_factor;
-                stroke.line_cap = LineCap::Round;
-                stroke.line_join = LineJoin::Round;
-                let mut paint = Paint::default();
-                paint.set_color(self.draw_config.color.into());
-
-                pixmap.stroke_path(
-                    &path,
-                    &paint,
-                    &stroke,
-                    tiny_skia::Transform::identity(),
-                    None,
-                );
-            }
-            _ => {}
+        if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
         }
     }
 }
 
-impl Mobject for SimpleLine {}
+impl Mobject for SimpleLine {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points([self.p0, self.p1])
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        vec![self.p0, self.p1]
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        if let [p0, p1] = points {
+            (self.p0, self.p1) = (*p0, *p1);
+        }
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        polyline_elements(&[self.p0, self.p1], false)
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
 
+#[derive(Clone)]
 pub struct PolyLine {
     pub points: Vec<Point3<GMFloat>>,
     pub draw_config: DrawConfig,
@@ -216,9 +710,11 @@ impl Transform for PolyLine {
         for p in &mut self.points {
             *p = transform * (*p);
         }
+        self.draw_config.transform_gradients(transform);
     }
 }
 
+#[derive(Clone)]
 pub struct Arc {
     center_point: Point3<GMFloat>,
     start_angle: GMFloat,
@@ -236,138 +732,180 @@ impl Arc {
         end_angle: GMFloat,
         radius: GMFloat,
     ) -> Self {
-        let _segs = ((end_angle - start_angle) / (PI as GMFloat / 2 as GMFloat)).ceil() as usize;
-        let delta_angle = (end_angle - start_angle) / _segs as GMFloat;
-        let mut _seg_list = vec![];
-        _seg_list.push(start_angle);
-        for i in 1..(_segs - 1) {
-            _seg_list.push(start_angle + i as GMFloat * delta_angle);
-        }
-        _seg_list.push(end_angle);
+        let _seg_list = segment_angles(start_angle, end_angle);
         Self {
             center_point,
             start_angle,
             end_angle,
             radius,
-            _segs,
+            _segs: _seg_list.len() - 1,
             _seg_list,
             draw_config: DrawConfig::default(),
         }
     }
+
+    pub fn center(&self) -> Point3<GMFloat> {
+        self.center_point
+    }
+
+    pub fn radius(&self) -> GMFloat {
+        self.radius
+    }
+
+    pub fn set_radius(&mut self, radius: GMFloat) {
+        self.radius = radius;
+    }
+
+    /// start and end angle in radians, counterclockwise from the x axis
+    pub fn angles(&self) -> (GMFloat, GMFloat) {
+        (self.start_angle, self.end_angle)
+    }
+
+    /// an end angle below the start angle draws the arc clockwise
+    pub fn set_angles(&mut self, start_angle: GMFloat, end_angle: GMFloat) {
+        (self.start_angle, self.end_angle) = (start_angle, end_angle);
+        self._seg_list = segment_angles(start_angle, end_angle);
+        self._segs = self._seg_list.len() - 1;
+    }
+}
+
+/// split angles from `start_angle` to `end_angle`, at most a quarter turn apart and
+/// always at least one segment
+fn segment_angles(start_angle: GMFloat, end_angle: GMFloat) -> Vec<GMFloat> {
+    let segs = ((end_angle - start_angle).abs() / (PI as GMFloat / 2.0))
+        .ceil()
+        .max(1.0) as usize;
+    let delta_angle = (end_angle - start_angle) / segs as GMFloat;
+    let mut angles = vec![start_angle];
+    for i in 1..segs {
+        angles.push(start_angle + i as GMFloat * delta_angle);
+    }
+    angles.push(end_angle);
+    angles
 }
 
 impl Draw for Arc {
     fn draw(&self, ctx: &mut Context) {
-        let scale_factor = ctx.scene_config.scale_factor;
-        let scene_width = ctx.scene_config.width;
-        let scene_height = ctx.scene_config.height;
-        match &mut ctx.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                for i in 0..(self._segs - 1) {
-                    let mut pb = tiny_skia::PathBuilder::new();
-                    // approximate arc by cubic bezier curve here
-                    let start_angle = self._seg_list[i];
-                    let end_angle = self._seg_list[i + 1];
-                    let k = k_for_bezier_arc((end_angle - start_angle) / 2.0);
-                    let point_0 = self.center_point.xy()
-                        + Vector2::new(end_angle.cos(), end_angle.sin()) * self.radius;
-
-                    let point_3 = self.center_point.xy()
-                        + Vector2::new(start_angle.cos(), start_angle.sin()) * self.radius;
-
-                    let point_1 =
-                        point_0 + Vector2::new(end_angle.sin(), -end_angle.cos()) * k * self.radius;
-                    let point_2 = point_3
-                        + Vector2::new(-start_angle.sin(), start_angle.cos()) * k * self.radius;
-                    pb.move_to(
-                        coordinate_change_x(point_0.x, scene_width) * scale_factor,
-                        coordinate_change_y(point_0.y, scene_height) * scale_factor,
-                    );
-                    pb.cubic_to(
-                        coordinate_change_x(point_1.x, scene_width) * scale_factor,
-                        coordinate_change_y(point_1.y, scene_height) * scale_factor,
-                        coordinate_change_x(point_2.x, scene_width) * scale_factor,
-                        coordinate_change_y(point_2.y, scene_height) * scale_factor,
-                        coordinate_change_x(point_3.x, scene_width) * scale_factor,
-                        coordinate_change_y(point_3.y, scene_height) * scale_factor,
-                    );
-
-                    let path = pb.finish().unwrap();
-                    let mut stroke = Stroke::default();
-                    stroke.width = self.draw_config.stoke_width * scale_factor;
-                    stroke.line_cap = LineCap::Round;
-                    stroke.line_join = LineJoin::Round;
-                    let mut paint = Paint::default();
-                    paint.set_color(self.draw_config.color.into());
-
-                    pixmap.stroke_path(
-                        &path,
-                        &paint,
-                        &stroke,
-                        tiny_skia::Transform::identity(),
-                        None,
-                    );
-                }
-            }
-            _ => {}
+        if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
         }
     }
 }
 
 impl Transform for Arc {
-    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {}
+    /// moves, rotates, mirrors and scales the arc; an uneven scale or a shear can't keep
+    /// it circular, so the radius takes their average scale
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        let x_axis = transform * Vector3::x();
+        let y_axis = transform * Vector3::y();
+        let det = x_axis.x * y_axis.y - x_axis.y * y_axis.x;
+        let rotation = x_axis.y.atan2(x_axis.x);
+        let (start_angle, end_angle) = if det < 0.0 {
+            // mirrored, swap the ends so the arc still runs counter-clockwise
+            (rotation - self.end_angle, rotation - self.start_angle)
+        } else {
+            (rotation + self.start_angle, rotation + self.end_angle)
+        };
+        self.set_angles(start_angle, end_angle);
+        self.center_point = transform * self.center_point;
+        self.radius *= det.abs().sqrt();
+        self.draw_config.transform_gradients(transform);
+    }
 }
 
-impl Mobject for Arc {}
+impl Mobject for Arc {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        // the extremes lie at the ends or where the arc crosses an axis direction
+        let mut angles = vec![self.start_angle, self.end_angle];
+        let quarter = PI as GMFloat / 2.0;
+        // reversed arcs run clockwise, walk the axis directions between the ends either way
+        let (low, high) = (
+            self.start_angle.min(self.end_angle),
+            self.start_angle.max(self.end_angle),
+        );
+        let mut a = (low / quarter).ceil() * quarter;
+        while a < high {
+            angles.push(a);
+            a += quarter;
+        }
+        BoundingBox::from_points(angles.into_iter().map(|a| {
+            self.center_point + Vector3::new(a.cos(), a.sin(), 0.0) * self.radius
+        }))
+    }
+
+    /// only the center, so interpolating control points moves the arc but keeps its
+    /// radius and angles, change those with `set_radius` and `set_angles`
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        vec![self.center_point]
+    }
 
-impl Draw for PolyLine {
-    fn draw(self: &Self, ctx: &mut Context) {
-        if self.points.len() < 2 {
-            return;
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        if let [center] = points {
+            self.center_point = *center;
         }
+    }
 
-        let scale_factor = ctx.scene_config.scale_factor;
+    /// one cubic bezier per segment
+    fn path_elements(&self) -> Vec<PathElement> {
+        let point = |angle: GMFloat| {
+            self.center_point + Vector3::new(angle.cos(), angle.sin(), 0.0) * self.radius
+        };
+        let tangent = |angle: GMFloat| Vector3::new(-angle.sin(), angle.cos(), 0.0) * self.radius;
+        let mut elements = vec![PathElement::MoveTo(point(self.start_angle))];
+        for w in self._seg_list.windows(2) {
+            let (start_angle, end_angle) = (w[0], w[1]);
+            let k = k_for_bezier_arc((end_angle - start_angle) / 2.0);
+            elements.push(PathElement::CubicTo(
+                point(start_angle) + tangent(start_angle) * k,
+                point(end_angle) - tangent(end_angle) * k,
+                point(end_angle),
+            ));
+        }
+        elements
+    }
 
-        match &mut ctx.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                let mut pb = tiny_skia::PathBuilder::new();
-                let p0 = (
-                    coordinate_change_x(self.points[0][(0)], ctx.scene_config.width) * scale_factor,
-                    coordinate_change_y(self.points[0][(1)], ctx.scene_config.height)
-                        * scale_factor,
-                );
-                pb.move_to(p0.0 as f32, p0.1 as f32);
-                for p in self.points[1..].iter() {
-                    let point = (
-                        coordinate_change_x(p[(0)], ctx.scene_config.width) * scale_factor,
-                        coordinate_change_y(p[(1)], ctx.scene_config.height) * scale_factor,
-                    );
-                    pb.line_to(point.0 as f32, point.1 as f32);
-                }
-                let path = pb.finish().unwrap();
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
 
-                let mut stroke = Stroke::default();
-                stroke.width = self.draw_config.stoke_width * scale_factor;
-                stroke.line_cap = LineCap::Round;
-                stroke.line_join = LineJoin::Round;
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
 
-                let mut paint = Paint::default();
-                paint.set_color(self.draw_config.color.into());
-
-                pixmap.stroke_path(
-                    &path,
-                    &paint,
-                    &stroke,
-                    tiny_skia::Transform::identity(),
-                    None,
-                );
-            }
-            _ => {}
+impl Draw for PolyLine {
+    fn draw(self: &Self, ctx: &mut Context) {
+        if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
         }
     }
 }
 
-impl Mobject for PolyLine {}
+impl Mobject for PolyLine {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.points.iter().copied())
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        self.points.clone()
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        self.points = points.to_vec();
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        polyline_elements(&self.points, false)
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
 
 pub fn rotate_matrix(axis: Vector3<GMFloat>, theta: GMFloat) {
     //assume axis is a unit vector
@@ -388,6 +926,176 @@ fn test_draw_arc() {
     let mut ctx = Context::default();
     let mut scene = Scene::default();
     let arc = Arc::new(Point3::new(0.0, 1.0, 0.0), 0.0, PI as GMFloat * 2.0, 3.0);
+    // a full turn is four quarter segments, each bulging out to the circle
+    let mut start = Point3::origin();
+    let mut segments = 0;
+    for e in arc.path_elements() {
+        match e {
+            PathElement::MoveTo(p) => start = p,
+            PathElement::CubicTo(c1, c2, end) => {
+                let mid = (start.coords + (c1.coords + c2.coords) * 3.0 + end.coords) / 8.0;
+                assert!(((mid - arc.center_point.coords).norm() - 3.0).abs() < 1e-3);
+                start = end;
+                segments += 1;
+            }
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!(segments, 4);
+    for (from, to) in [(0.0, 0.0), (PI as GMFloat, 0.0)] {
+        let arc = Arc::new(Point3::origin(), from, to, 1.0);
+        assert!(arc.path_elements().iter().all(|e| match e {
+            PathElement::CubicTo(c1, c2, p) => [c1, c2, p].iter().all(|p| p.x.is_finite()),
+            _ => true,
+        }));
+    }
+    scene.add(Box::new(arc));
+
+    // a quarter arc follows rotations, mirroring, scaling and moves, gradient included
+    let mut arc = Arc::new(Point3::origin(), 0.0, PI as GMFloat / 2.0, 1.0);
+    arc.draw_config = arc
+        .draw_config
+        .with_fill_gradient(gradient::Gradient::linear(
+            Point3::origin(),
+            Point3::new(1.0, 0.0, 0.0),
+            vec![],
+        ));
+    let rotation = nalgebra::Rotation3::from_axis_angle(&Vector3::z_axis(), PI as GMFloat / 2.0);
+    let rotation = rotation.to_homogeneous();
+    arc.transform(nalgebra::Transform::from_matrix_unchecked(rotation));
+    let b = arc.bounding_box().unwrap();
+    assert!((b.min.x + 1.0).abs() < 1e-5 && b.max.x.abs() < 1e-5 && (b.max.y - 1.0).abs() < 1e-5);
+    let mirror = nalgebra::Matrix4::new_nonuniform_scaling(&Vector3::new(1.0, -1.0, 1.0));
+    arc.transform(nalgebra::Transform::from_matrix_unchecked(mirror));
+    let b = arc.bounding_box().unwrap();
+    assert!((b.min.y + 1.0).abs() < 1e-5 && b.max.y.abs() < 1e-5);
+    arc.scale(2.0);
+    arc.move_this(Vector3::new(3.0, 0.0, 0.0));
+    let b = arc.bounding_box().unwrap();
+    assert!((b.min - Point3::new(1.0, -2.0, 0.0)).norm() < 1e-5);
+    assert!((b.max - Point3::new(3.0, 0.0, 0.0)).norm() < 1e-5);
+    let gradient = arc.draw_config.fill_gradient.as_ref().unwrap();
+    let end = gradient.transform * Point3::new(1.0, 0.0, 0.0);
+    assert!((end - Point3::new(3.0, -2.0, 0.0)).norm() < 1e-5);
     scene.add(Box::new(arc));
     scene.save_png(&mut ctx, "arc.png");
 }
+
+#[test]
+fn test_bounding_box() {
+    use crate::math_utils::constants::{DR, UL};
+    use group::MobjectGroup;
+    let group = MobjectGroup::new(vec![
+        Box::new(Rectangle::default()),
+        Box::new(SimpleLine {
+            p0: Point3::new(-1.0, 2.0, 0.0),
+            p1: Point3::new(0.5, 0.5, 0.0),
+            ..Default::default()
+        }),
+    ]);
+    assert_eq!(group.width(), 2.0);
+    assert_eq!(group.height(), 2.0);
+    assert_eq!(group.depth(), 0.0);
+    assert_eq!(group.get_center(), Point3::new(0.0, 1.0, 0.0));
+    assert_eq!(group.get_corner(UL), Point3::new(-1.0, 2.0, 0.0));
+    assert_eq!(group.get_corner(DR), Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(group.get_top(), Point3::new(0.0, 2.0, 0.0));
+    let arc = Arc::new(Point3::origin(), 0.0, PI as GMFloat, 1.0);
+    let b = arc.bounding_box().unwrap();
+    assert!((b.max.y - 1.0).abs() < 1e-5 && (b.min.x + 1.0).abs() < 1e-5);
+    // the same half circle drawn clockwise
+    let mut reversed = Arc::new(Point3::origin(), PI as GMFloat, 0.0, 1.0);
+    assert_eq!(reversed.bounding_box(), Some(b));
+    reversed.set_angles(0.0, -PI as GMFloat / 2.0);
+    reversed.set_radius(2.0);
+    let b = reversed.bounding_box().unwrap();
+    assert!((b.min.y + 2.0).abs() < 1e-5 && (b.max.x - 2.0).abs() < 1e-5);
+    assert!(b.max.y.abs() < 1e-5 && b.min.x.abs() < 1e-5);
+    assert!(MobjectGroup::new(vec![]).bounding_box().is_none());
+}
+
+#[test]
+fn test_relative_positioning() {
+    use crate::math_utils::constants::{
+        DEFAULT_MOBJECT_TO_EDGE_BUFFER, DEFAULT_MOBJECT_TO_MOBJECT_BUFFER, DL, UL,
+    };
+    let scene_config = SceneConfig::default();
+    let anchor = Rectangle::default();
+    let mut r = Rectangle::default();
+    r.scale(0.5);
+    r.next_to(&anchor, RIGHT, DEFAULT_MOBJECT_TO_MOBJECT_BUFFER);
+    assert_eq!(r.get_left(), Point3::new(1.25, 0.5, 0.0));
+    r.align_to(&anchor, UP);
+    assert_eq!(r.get_top().y, 1.0);
+    assert_eq!(r.get_left().x, 1.25);
+    r.move_to(Point3::new(3.0, 3.0, 0.0));
+    assert_eq!(r.get_center(), Point3::new(3.0, 3.0, 0.0));
+    r.to_edge(LEFT, DEFAULT_MOBJECT_TO_EDGE_BUFFER, &scene_config);
+    assert_eq!(r.get_left().x, -7.5);
+    assert_eq!(r.get_center().y, 3.0);
+    r.to_corner(UL, DEFAULT_MOBJECT_TO_EDGE_BUFFER, &scene_config);
+    assert_eq!(r.get_corner(UL), Point3::new(-7.5, 4.0, 0.0));
+    r.move_to_aligned(Point3::origin(), DL);
+    assert_eq!(r.get_corner(DL), Point3::origin());
+}
+
+#[test]
+fn test_submobjects() {
+    use group::MobjectGroup;
+    use text::Text;
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let mut text = Text::new(
+        "ab c".to_owned(),
+        Point3::new(-3.0, 1.0, 0.0),
+        16.0,
+        DrawConfig::filled(Color::default()),
+    );
+    // the space has no outline
+    assert_eq!(text.submobjects().len(), 3);
+    assert_eq!(text[2].character, 'c');
+    assert!(text[0].get_right().x <= text[1].get_left().x);
+    text[1].draw_config = Some(DrawConfig::filled(Color::new(0xff, 0x00, 0x00, 0xff)));
+    for g in &mut text[1..] {
+        g.move_this(Vector3::new(0.0, -0.5, 0.0));
+    }
+
+    let inner = MobjectGroup::new(vec![Box::new(Rectangle::default())]).with_name("inner");
+    let mut root = MobjectGroup::new(vec![Box::new(text), Box::new(inner)]);
+    assert_eq!(root.descendants().len(), 6);
+    assert_eq!(root.submobject_range(1..5).len(), 1);
+    assert!(root.find_by_name("inner").is_some());
+    root.find_by_name_mut("inner")
+        .unwrap()
+        .move_this(Vector3::new(2.0, 0.0, 0.0));
+    assert_eq!(root[1].get_left().x, 2.0);
+    let mut count = 0;
+    root.for_each_descendant_mut(&mut |_| count += 1);
+    assert_eq!(count, 6);
+
+    scene.add(Box::new(root));
+    scene.save_png(&mut ctx, "submobjects.png");
+}
+
+#[test]
+fn test_fill_and_stroke() {
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let mut square = Rectangle::default();
+    square.scale(2.0);
+    square.draw_config = DrawConfig {
+        line_join: LineJoin::Miter,
+        ..DrawConfig::stroked(Color::new(0xff, 0xff, 0xff, 0xff), 0.1)
+    }
+    .with_fill(Color::new(0xff, 0x00, 0x00, 0xff), 0.5);
+    let mut arc = Arc::new(Point3::new(-3.0, 0.0, 0.0), 0.0, PI as GMFloat, 1.5);
+    let style = arc.draw_config_mut().unwrap();
+    style.fill_color = Color::new(0x00, 0xff, 0x00, 0xff);
+    style.fill_opacity = 1.0;
+    style.line_cap = LineCap::Butt;
+    assert!(arc.draw_config().unwrap().has_fill());
+    assert!(!DrawConfig::filled(Color::default()).has_stroke());
+    scene.add(Box::new(square));
+    scene.add(Box::new(arc));
+    scene.save_png(&mut ctx, "fill_and_stroke.png");
+}
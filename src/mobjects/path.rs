@@ -1,6 +1,11 @@
-use crate::GMFloat;
+use nalgebra::Point3;
 
-#[derive(Debug)]
+use crate::math_utils::{constants::PI, k_for_bezier_arc};
+use crate::{Context, GMFloat, SceneConfig};
+
+use super::{BoundingBox, Draw, DrawConfig, Mobject, Transform};
+
+#[derive(Debug, Clone)]
 pub enum PathElement {
     MoveTo(nalgebra::Point3<GMFloat>),
     LineTo(nalgebra::Point3<GMFloat>),
@@ -13,7 +18,481 @@ pub enum PathElement {
     Close,
 }
 
+/// all end and control points, the curves lie inside their hull
+pub fn control_points(elements: &[PathElement]) -> Vec<nalgebra::Point3<GMFloat>> {
+    elements
+        .iter()
+        .flat_map(|e| match e {
+            PathElement::MoveTo(p) | PathElement::LineTo(p) => vec![*p],
+            PathElement::QuadTo(p1, p2) => vec![*p1, *p2],
+            PathElement::CubicTo(p1, p2, p3) => vec![*p1, *p2, *p3],
+            PathElement::Close => vec![],
+        })
+        .collect()
+}
+
+/// write `points` back in `control_points` order, extra elements keep their points
+pub fn set_control_points(elements: &mut [PathElement], points: &[nalgebra::Point3<GMFloat>]) {
+    let mut points = points.iter();
+    for e in elements {
+        let targets = match e {
+            PathElement::MoveTo(p) | PathElement::LineTo(p) => vec![p],
+            PathElement::QuadTo(p1, p2) => vec![p1, p2],
+            PathElement::CubicTo(p1, p2, p3) => vec![p1, p2, p3],
+            PathElement::Close => vec![],
+        };
+        for (target, point) in targets.into_iter().zip(&mut points) {
+            *target = *point;
+        }
+    }
+}
+
+/// the elements in pixel coordinates of `scene_config`, None when nothing is drawn
+pub(crate) fn to_pixel_path(
+    elements: &[PathElement],
+    scene_config: &SceneConfig,
+) -> Option<tiny_skia::Path> {
+    let x = |p: &nalgebra::Point3<GMFloat>| scene_config.convert_coord_x(p.x);
+    let y = |p: &nalgebra::Point3<GMFloat>| scene_config.convert_coord_y(p.y);
+    let mut pb = tiny_skia::PathBuilder::new();
+    for e in elements {
+        match e {
+            PathElement::MoveTo(p) => pb.move_to(x(p), y(p)),
+            PathElement::LineTo(p) => pb.line_to(x(p), y(p)),
+            PathElement::QuadTo(p1, p2) => pb.quad_to(x(p1), y(p1), x(p2), y(p2)),
+            PathElement::CubicTo(p1, p2, p3) => {
+                pb.cubic_to(x(p1), y(p1), x(p2), y(p2), x(p3), y(p3))
+            }
+            PathElement::Close => pb.close(),
+        }
+    }
+    pb.finish()
+}
+
+/// straight segments through `points`, back to the first one when `closed`
+pub fn polyline_elements(points: &[Point3<GMFloat>], closed: bool) -> Vec<PathElement> {
+    let mut elements: Vec<PathElement> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| match i {
+            0 => PathElement::MoveTo(*p),
+            _ => PathElement::LineTo(*p),
+        })
+        .collect();
+    if closed && !elements.is_empty() {
+        elements.push(PathElement::Close);
+    }
+    elements
+}
+
+/// pieces each curve is cut into when flattening
+const CURVE_SAMPLES: usize = 16;
 
-pub struct Path{
+/// every subpath as a polyline, closed subpaths end at their start again
+pub fn flatten(elements: &[PathElement]) -> Vec<Vec<Point3<GMFloat>>> {
+    let mut subpaths = vec![];
+    let mut current: Vec<Point3<GMFloat>> = vec![];
+    for e in elements {
+        match e {
+            PathElement::MoveTo(p) => {
+                if current.len() > 1 {
+                    subpaths.push(current);
+                }
+                current = vec![*p];
+            }
+            PathElement::LineTo(p) => current.push(*p),
+            PathElement::QuadTo(p1, p2) => {
+                let p0 = current.last().copied().unwrap_or(*p1);
+                current.extend((1..=CURVE_SAMPLES).map(|i| {
+                    let t = i as GMFloat / CURVE_SAMPLES as GMFloat;
+                    p0.lerp(p1, t).lerp(&p1.lerp(p2, t), t)
+                }));
+            }
+            PathElement::CubicTo(p1, p2, p3) => {
+                let p0 = current.last().copied().unwrap_or(*p1);
+                current.extend((1..=CURVE_SAMPLES).map(|i| {
+                    let t = i as GMFloat / CURVE_SAMPLES as GMFloat;
+                    let (a, b, c) = (p0.lerp(p1, t), p1.lerp(p2, t), p2.lerp(p3, t));
+                    a.lerp(&b, t).lerp(&b.lerp(&c, t), t)
+                }));
+            }
+            PathElement::Close => {
+                let Some(start) = current.first().copied() else {
+                    continue;
+                };
+                current.push(start);
+                subpaths.push(current);
+                current = vec![start];
+            }
+        }
+    }
+    if current.len() > 1 {
+        subpaths.push(current);
+    }
+    subpaths
+}
 
-}
\ No newline at end of file
+fn cumulative_lengths(points: &[Point3<GMFloat>]) -> Vec<GMFloat> {
+    let mut length = 0.0;
+    let mut lengths = vec![0.0];
+    for w in points.windows(2) {
+        length += (w[1] - w[0]).norm();
+        lengths.push(length);
+    }
+    lengths
+}
+
+/// total length of all subpaths
+pub fn arc_length(elements: &[PathElement]) -> GMFloat {
+    flatten(elements)
+        .iter()
+        .filter_map(|points| cumulative_lengths(points).last().copied())
+        .sum()
+}
+
+/// point `distance` along a polyline with the given cumulative lengths
+fn point_at(points: &[Point3<GMFloat>], lengths: &[GMFloat], distance: GMFloat) -> Point3<GMFloat> {
+    let i = lengths
+        .partition_point(|l| *l < distance)
+        .clamp(1, points.len() - 1);
+    let (l0, l1) = (lengths[i - 1], lengths[i]);
+    let t = if l1 > l0 {
+        (distance - l0) / (l1 - l0)
+    } else {
+        0.0
+    };
+    points[i - 1].lerp(&points[i], t)
+}
+
+/// cut the path into `num_dashes` dashes evenly spread by arc length, each taking
+/// `dashed_ratio` of its slot; curves come out flattened into line segments
+pub fn dash_elements(
+    elements: &[PathElement],
+    num_dashes: usize,
+    dashed_ratio: GMFloat,
+) -> Vec<PathElement> {
+    let total = arc_length(elements);
+    if num_dashes == 0 || total <= 0.0 {
+        return vec![];
+    }
+    let period = total / num_dashes as GMFloat;
+    let dash = period * dashed_ratio.clamp(0.0, 1.0);
+    let mut dashed = vec![];
+    // arc length where the current subpath starts
+    let mut start = 0.0;
+    for points in flatten(elements) {
+        let lengths = cumulative_lengths(&points);
+        let length = lengths[lengths.len() - 1];
+        for k in (start / period).floor() as usize..num_dashes {
+            let a = k as GMFloat * period - start;
+            if a >= length {
+                break;
+            }
+            let (a, b) = (a.max(0.0), (a + dash).min(length));
+            if b <= a {
+                continue;
+            }
+            dashed.push(PathElement::MoveTo(point_at(&points, &lengths, a)));
+            dashed.extend(
+                points
+                    .iter()
+                    .zip(&lengths)
+                    .filter(|(_, l)| **l > a && **l < b)
+                    .map(|(p, _)| PathElement::LineTo(*p)),
+            );
+            dashed.push(PathElement::LineTo(point_at(&points, &lengths, b)));
+        }
+        start += length;
+    }
+    dashed
+}
+
+/// free form path made of [`PathElement`]s
+#[derive(Debug, Clone, Default)]
+pub struct Path {
+    pub elements: Vec<PathElement>,
+    pub draw_config: DrawConfig,
+}
+
+impl Path {
+    pub fn new(elements: Vec<PathElement>) -> Self {
+        Self {
+            elements,
+            draw_config: DrawConfig::default(),
+        }
+    }
+
+    /// `width` by `height` centered at origin with quarter circle corners, the radius
+    /// is capped at half the shorter side; filled like a polygon
+    pub fn rounded_rectangle(width: GMFloat, height: GMFloat, corner_radius: GMFloat) -> Self {
+        let r = corner_radius.clamp(0.0, width.min(height) / 2.0);
+        // where the straight sides end
+        let (a, b) = (width / 2.0 - r, height / 2.0 - r);
+        let (w, h) = (width / 2.0, height / 2.0);
+        // control point distance of a cubic quarter circle
+        let k = r * k_for_bezier_arc(PI / 4.0);
+        let p = |x: GMFloat, y: GMFloat| Point3::new(x, y, 0.0);
+        let mut elements = vec![PathElement::MoveTo(p(-a, -h))];
+        // counterclockwise from the bottom side, each side then the corner after it
+        let corners = [
+            (p(a, -h), p(a + k, -h), p(w, -b - k), p(w, -b)),
+            (p(w, b), p(w, b + k), p(a + k, h), p(a, h)),
+            (p(-a, h), p(-a - k, h), p(-w, b + k), p(-w, b)),
+            (p(-w, -b), p(-w, -b - k), p(-a - k, -h), p(-a, -h)),
+        ];
+        for (side_end, c1, c2, corner_end) in corners {
+            elements.push(PathElement::LineTo(side_end));
+            if r > 0.0 {
+                elements.push(PathElement::CubicTo(c1, c2, corner_end));
+            }
+        }
+        elements.push(PathElement::Close);
+        Self {
+            elements,
+            draw_config: DrawConfig::filled(crate::theme::theme().primary),
+        }
+    }
+
+    /// `main` with `holes` punched through it, styled like `main`; the holes may sit
+    /// anywhere inside, see [`super::boolean::BooleanOp`] for shapes that overlap
+    pub fn cutout(main: &dyn Mobject, holes: &[&dyn Mobject]) -> Self {
+        let mut elements = main.path_elements();
+        elements.extend(holes.iter().flat_map(|h| h.path_elements()));
+        let mut draw_config = main.draw_config().cloned().unwrap_or_default();
+        draw_config.fill_rule = tiny_skia::FillRule::EvenOdd;
+        Self {
+            elements,
+            draw_config,
+        }
+    }
+}
+
+impl Transform for Path {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        for e in &mut self.elements {
+            e.transform(transform);
+        }
+        self.draw_config.transform_gradients(transform);
+    }
+}
+
+impl Draw for Path {
+    fn draw(&self, ctx: &mut Context) {
+        if let Some(path) = to_pixel_path(&self.elements, &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
+        }
+    }
+}
+
+impl Mobject for Path {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(control_points(&self.elements))
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        control_points(&self.elements)
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        set_control_points(&mut self.elements, points);
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        self.elements.clone()
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
+
+/// dashed copy of the outline of any mobject, see [`dash_elements`]
+#[derive(Debug, Clone)]
+pub struct DashedVMobject {
+    pub path: Path,
+    pub num_dashes: usize,
+    pub dashed_ratio: GMFloat,
+}
+
+impl DashedVMobject {
+    /// keeps the stroke of `mobject`, one without a stroke is dashed in its fill color
+    pub fn new(mobject: &dyn Mobject, num_dashes: usize, dashed_ratio: GMFloat) -> Self {
+        let mut draw_config = mobject.draw_config().cloned().unwrap_or_default();
+        if !draw_config.has_stroke() {
+            draw_config.stroke_color = draw_config.fill_color;
+            draw_config.stroke_width = DrawConfig::default().stroke_width;
+            draw_config.stroke_opacity = 1.0;
+        }
+        draw_config.fill_opacity = 0.0;
+        Self {
+            path: Path {
+                elements: dash_elements(&mobject.path_elements(), num_dashes, dashed_ratio),
+                draw_config,
+            },
+            num_dashes,
+            dashed_ratio,
+        }
+    }
+}
+
+impl Transform for DashedVMobject {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.path.transform(transform);
+    }
+}
+
+impl Draw for DashedVMobject {
+    fn draw(&self, ctx: &mut Context) {
+        self.path.draw(ctx);
+    }
+}
+
+impl Mobject for DashedVMobject {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.path.bounding_box()
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        self.path.control_points()
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        self.path.set_control_points(points);
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        self.path.path_elements()
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        self.path.draw_config()
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        self.path.draw_config_mut()
+    }
+}
+
+/// straight line from `p0` to `p1` drawn as `num_dashes` dashes, which keep fitting it
+/// end to end as it moves
+#[derive(Debug, Clone)]
+pub struct DashedLine {
+    pub p0: Point3<GMFloat>,
+    pub p1: Point3<GMFloat>,
+    pub num_dashes: usize,
+    /// part of each dash slot that is drawn, the rest is the gap after it
+    pub dashed_ratio: GMFloat,
+    pub draw_config: DrawConfig,
+}
+
+impl DashedLine {
+    /// as many dashes of about `dash_length` as fit, with `dashed_ratio` of the line drawn
+    pub fn new(
+        start: Point3<GMFloat>,
+        end: Point3<GMFloat>,
+        dash_length: GMFloat,
+        dashed_ratio: GMFloat,
+    ) -> Self {
+        let drawn = (end - start).norm() * dashed_ratio;
+        Self {
+            p0: start,
+            p1: end,
+            num_dashes: (drawn / dash_length).round().max(1.0) as usize,
+            dashed_ratio,
+            draw_config: DrawConfig::default(),
+        }
+    }
+}
+
+impl Transform for DashedLine {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.p0 = transform * self.p0;
+        self.p1 = transform * self.p1;
+        self.draw_config.transform_gradients(transform);
+    }
+}
+
+impl Draw for DashedLine {
+    fn draw(&self, ctx: &mut Context) {
+        if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
+        }
+    }
+}
+
+impl Mobject for DashedLine {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points([self.p0, self.p1])
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        vec![self.p0, self.p1]
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        if let [p0, p1] = points {
+            (self.p0, self.p1) = (*p0, *p1);
+        }
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        let line = [PathElement::MoveTo(self.p0), PathElement::LineTo(self.p1)];
+        dash_elements(&line, self.num_dashes, self.dashed_ratio)
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
+
+#[test]
+fn test_dashes() {
+    use super::{Arc, Rectangle};
+    use crate::{Color, Scene};
+
+    let square = Rectangle::default();
+    assert!((arc_length(&square.path_elements()) - 4.0).abs() < 1e-5);
+    let dashed = DashedVMobject::new(&square, 5, 0.5);
+    let dashes = flatten(&dashed.path_elements());
+    assert_eq!(dashes.len(), 5);
+    for dash in &dashes {
+        assert!((arc_length(&polyline_elements(dash, false)) - 0.4).abs() < 1e-4);
+    }
+    // the dash across the corner keeps the corner
+    assert!(dashes.iter().any(|d| d.len() == 3));
+
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let mut circle = Arc::new(Point3::origin(), 0.0, 2.0 * std::f32::consts::PI, 2.0);
+    circle.draw_config_mut().unwrap().stroke_width = 0.05;
+    scene.add(Box::new(DashedVMobject::new(&circle, 20, 0.6)));
+    let mut ants = Rectangle::default();
+    ants.scale(3.0);
+    ants.move_this(nalgebra::Vector3::new(-7.0, -1.5, 0.0));
+    ants.draw_config =
+        DrawConfig::stroked(Color::new(0xff, 0xff, 0xff, 0xff), 0.05).with_dash(&[0.2, 0.1], 0.0);
+    scene.add(Box::new(ants.clone()));
+    // marching ants move the offset a little every frame
+    ants.move_this(nalgebra::Vector3::new(10.5, 0.0, 0.0));
+    ants.draw_config.dash_offset = 0.15;
+    scene.add(Box::new(ants));
+    // dashes of a line stretch along with it
+    let mut line = DashedLine::new(Point3::origin(), Point3::new(2.0, 0.0, 0.0), 0.2, 0.5);
+    assert_eq!(line.num_dashes, 5);
+    line.draw_config.stroke_width = 0.05;
+    line.scale(2.0);
+    line.move_this(nalgebra::Vector3::new(-2.0, 3.0, 0.0));
+    let dashes = flatten(&line.path_elements());
+    assert_eq!(dashes.len(), 5);
+    assert!((arc_length(&polyline_elements(&dashes[0], false)) - 0.4).abs() < 1e-4);
+    scene.add(Box::new(line));
+    scene.save_png(&mut ctx, "dashes.png");
+}
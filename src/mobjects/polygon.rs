@@ -1,48 +1,81 @@
 use nalgebra::Point3;
-use tiny_skia::{FillRule, Paint, Shader};
+use tiny_skia::FillRule;
 
+use crate::math_utils::constants::PI;
 use crate::{Color, Context, GMFloat, GMPoint, Scene};
 
-use super::{Draw, DrawConfig, Mobject, Transform};
+use super::path::{polyline_elements, to_pixel_path, PathElement};
+use super::{BoundingBox, Draw, DrawConfig, Mobject, Transform};
 
-struct Polygon {
-    vertices: Vec<GMPoint>,
-    draw_config: DrawConfig,
+/// closed shape through `vertices`, filled with the theme primary color by the even-odd rule
+#[derive(Clone)]
+pub struct Polygon {
+    pub vertices: Vec<GMPoint>,
+    pub draw_config: DrawConfig,
 }
 
 impl Polygon {
     pub fn new(vertices: Vec<GMPoint>) -> Self {
         Self {
             vertices,
-            draw_config: DrawConfig::default(),
+            draw_config: DrawConfig {
+                fill_rule: FillRule::EvenOdd,
+                ..DrawConfig::filled(crate::theme::theme().primary)
+            },
         }
     }
+
+    /// `n` corners on the unit circle, odd ones point up and even ones stand on a side;
+    /// fewer than 3 corners make a triangle
+    pub fn regular(n: usize) -> Self {
+        let n = n.max(3);
+        let start = match n % 2 {
+            0 => PI / 2.0 + PI / n as GMFloat,
+            _ => PI / 2.0,
+        };
+        Self::new(circle_points(n, 1.0, start))
+    }
+
+    /// `n` points alternating between `outer_radius` and `inner_radius`, one point up;
+    /// at least 3 points
+    pub fn star(n: usize, inner_radius: GMFloat, outer_radius: GMFloat) -> Self {
+        let n = n.max(3);
+        let outer = circle_points(n, outer_radius, PI / 2.0);
+        let inner = circle_points(n, inner_radius, PI / 2.0 + PI / n as GMFloat);
+        let points = outer.into_iter().zip(inner).flat_map(|(o, i)| [o, i]);
+        Self::new(points.collect())
+    }
+
+    /// equilateral, on the unit circle and pointing up
+    pub fn triangle() -> Self {
+        Self::regular(3)
+    }
+
+    /// axis aligned with sides of `side_length`
+    pub fn square(side_length: GMFloat) -> Self {
+        let h = side_length / 2.0;
+        Self::new(vec![
+            Point3::new(-h, -h, 0.0),
+            Point3::new(h, -h, 0.0),
+            Point3::new(h, h, 0.0),
+            Point3::new(-h, h, 0.0),
+        ])
+    }
+}
+
+/// `n` points evenly spread counterclockwise around the origin from `start_angle`
+fn circle_points(n: usize, radius: GMFloat, start_angle: GMFloat) -> Vec<GMPoint> {
+    (0..n)
+        .map(|i| {
+            let angle = start_angle + 2.0 * PI * i as GMFloat / n as GMFloat;
+            Point3::new(radius * angle.cos(), radius * angle.sin(), 0.0)
+        })
+        .collect()
 }
 impl Draw for Polygon {
     fn draw(&self, ctx: &mut crate::Context) {
-        match &mut ctx.ctx_type {
-            crate::ContextType::TinySKIA(pixmap) => {
-                let mut pb = tiny_skia::PathBuilder::new();
-                let mut v_list = self.vertices.iter();
-                let start = v_list.next().unwrap();
-
-                pb.move_to(ctx.scene_config.convert_coord_x(start.x), ctx.scene_config.convert_coord_y(start.y));
-                for p in v_list {
-                    pb.line_to(ctx.scene_config.convert_coord_x(p.x), ctx.scene_config.convert_coord_y(p.y));
-                }
-                pb.close();
-                let path = pb.finish().unwrap();
-                let mut paint = Paint::default();
-                paint.set_color(self.draw_config.color.into());
-                pixmap.fill_path(
-                    &path,
-                    &paint,
-                    FillRule::EvenOdd,
-                    tiny_skia::Transform::identity(),
-                    None,
-                );
-            }
-            _ => {}
+        if let Some(path) = to_pixel_path(&self.path_elements(), &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
         }
     }
 }
@@ -52,10 +85,35 @@ impl Transform for Polygon {
         for p in &mut self.vertices {
             *p = transform.transform_point(p);
         }
+        self.draw_config.transform_gradients(transform);
     }
 }
 
-impl Mobject for Polygon {}
+impl Mobject for Polygon {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.vertices.iter().copied())
+    }
+
+    fn control_points(&self) -> Vec<GMPoint> {
+        self.vertices.clone()
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        self.vertices = points.to_vec();
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        polyline_elements(&self.vertices, true)
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
 
 #[test]
 pub fn test_polygon() {
@@ -67,6 +125,64 @@ pub fn test_polygon() {
         GMPoint::new(1.0, 2.0, 0.0),
     ];
     let mut polygon = Polygon::new(v_list);
+    assert_eq!(polygon.draw_config.fill_rule, FillRule::EvenOdd);
     scene.add(Box::new(polygon));
     scene.save_png(&mut ctx, "output.png");
 }
+
+#[test]
+fn test_shapes() {
+    use super::path::Path;
+    use crate::ContextType;
+    use nalgebra::Vector3;
+
+    let triangle = Polygon::triangle();
+    assert!((triangle.vertices[0] - Point3::new(0.0, 1.0, 0.0)).norm() < 1e-6);
+    let hexagon = Polygon::regular(6);
+    let b = hexagon.bounding_box().unwrap();
+    assert!((b.width() - 2.0).abs() < 1e-5 && (b.min.y + b.max.y).abs() < 1e-5);
+    let star = Polygon::star(5, 0.4, 1.0);
+    assert_eq!(star.vertices.len(), 10);
+    // too few corners can't enclose anything
+    assert_eq!(Polygon::regular(0).vertices, triangle.vertices);
+    assert_eq!(Polygon::star(2, 0.4, 1.0).vertices.len(), 6);
+    let b = Polygon::square(2.0).bounding_box().unwrap();
+    assert_eq!((b.min.x, b.max.y), (-1.0, 1.0));
+    let rounded = Path::rounded_rectangle(3.0, 2.0, 0.5);
+    let b = rounded.bounding_box().unwrap();
+    assert_eq!((b.width(), b.height()), (3.0, 2.0));
+
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let mut shapes: Vec<Box<dyn Mobject>> = vec![
+        Box::new(triangle),
+        Box::new(hexagon),
+        Box::new(star),
+        Box::new(Polygon::square(2.0)),
+        Box::new(rounded),
+    ];
+    for (i, shape) in shapes.iter_mut().enumerate() {
+        shape.move_this(Vector3::new(-6.0 + 3.0 * i as GMFloat, 2.0, 0.0));
+    }
+    let mut hole = Polygon::star(5, 0.4, 1.0);
+    hole.scale(0.8);
+    let mut cutout = Path::cutout(&Polygon::square(3.0), &[&hole]);
+    cutout.move_this(Vector3::new(0.0, -2.0, 0.0));
+    shapes.push(Box::new(cutout));
+    for shape in shapes {
+        scene.add(shape);
+    }
+    scene.save_png(&mut ctx, "shapes.png");
+
+    // the middle of the star hole shows the background, the square around it doesn't
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let pixel_at = |x: GMFloat, y: GMFloat| {
+        let x = ctx.scene_config.convert_coord_x(x) as u32;
+        let y = ctx.scene_config.convert_coord_y(y) as u32;
+        pixmap.pixel(x, y).unwrap()
+    };
+    assert_ne!(pixel_at(0.0, -2.0), pixel_at(1.3, -3.3));
+    assert_eq!(pixel_at(0.0, -2.0), pixel_at(0.0, -3.8));
+}
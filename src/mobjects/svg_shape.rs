@@ -1,22 +1,28 @@
 use std::{fs, io::Read};
 
+use log::{error, info};
 use nalgebra::Vector2;
 use usvg::{tiny_skia_path::PathSegment, Group, Node, NodeExt, NodeKind, TreeParsing};
 
 use crate::{
     math_utils::{point2d_to_point3d, point3d_to_point2d},
-    Context, ContextType, GMFloat, Scene,
+    Color, Context, ContextType, GMFloat, Scene,
 };
+use tiny_skia::{FillRule, LineCap, LineJoin, SpreadMode};
 
 use super::{
-    coordinate_change_x, coordinate_change_y, group::MobjectGroup, Draw, DrawConfig, Mobject,
-    Transform, path::PathElement,
+    gradient::{Gradient, GradientKind, GradientStop},
+    image::{decode_image, ImageMobject},
+    group::MobjectGroup, Draw, DrawConfig, Mobject,
+    Transform, path::{control_points, set_control_points, to_pixel_path, PathElement}, BoundingBox,
 };
 
 
 
-#[derive(Debug)]
-struct SVGPath {
+/// one path element of an svg file, named after its id when it has one
+#[derive(Debug, Clone)]
+pub struct SVGPath {
+    pub name: Option<String>,
     elements: Vec<PathElement>,
     is_closed: bool,
     draw_config: DrawConfig,
@@ -25,6 +31,7 @@ struct SVGPath {
 impl SVGPath {
     fn new() -> Self {
         Self {
+            name: None,
             elements: vec![],
             is_closed: false,
             draw_config: Default::default(),
@@ -92,89 +99,47 @@ impl super::Transform for SVGPath {
         for e in &mut self.elements {
             e.transform(transform);
         }
+        self.draw_config.transform_gradients(transform);
     }
 }
 
 impl Draw for SVGPath {
     fn draw(&self, ctx: &mut crate::Context) {
-        let scale_factor = ctx.scene_config.scale_factor;
-        let scene_width = ctx.scene_config.width;
-        let scene_height = ctx.scene_config.height;
-        match &mut ctx.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                let mut pb = tiny_skia::PathBuilder::new();
-                for e in &self.elements {
-                    match e {
-                        PathElement::MoveTo(p) => {
-                            pb.move_to(
-                                coordinate_change_x(p.x as f32, ctx.scene_config.width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p.y as f32, ctx.scene_config.height as f32)
-                                    * scale_factor as f32,
-                            );
-                        }
-                        PathElement::LineTo(p) => {
-                            pb.line_to(
-                                coordinate_change_x(p.x as f32, ctx.scene_config.width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p.y as f32, ctx.scene_config.height as f32)
-                                    * scale_factor as f32,
-                            );
-                        }
-                        PathElement::QuadTo(p1, p2) => {
-                            pb.quad_to(
-                                coordinate_change_x(p1.x as f32, ctx.scene_config.width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p1.y as f32, ctx.scene_config.height as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_x(p2.x as f32, ctx.scene_config.width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p2.y as f32, ctx.scene_config.height as f32)
-                                    * scale_factor as f32,
-                            );
-                        }
-                        PathElement::CubicTo(p1, p2, p3) => {
-                            pb.cubic_to(
-                                coordinate_change_x(p1.x as f32, scene_width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p1.y as f32, scene_height as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_x(p2.x as f32, scene_width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p2.y as f32, scene_height as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_x(p3.x as f32, scene_width as f32)
-                                    * scale_factor as f32,
-                                coordinate_change_y(p3.y as f32, scene_height as f32)
-                                    * scale_factor as f32,
-                            );
-                        }
-                        PathElement::Close => {
-                            pb.close();
-                        }
-                    }
-                }
-                let path = pb.finish().unwrap();
-
-                let mut stroke = tiny_skia::Stroke::default();
-                stroke.width = self.draw_config.stoke_width * scale_factor;
-                stroke.line_cap = tiny_skia::LineCap::Round;
-                let mut paint = tiny_skia::Paint::default();
-                paint.set_color(self.draw_config.color.into());
-                pixmap.fill_path(
-                    &path,
-                    &paint,
-                    Default::default(),
-                    tiny_skia::Transform::identity(),
-                    None,
-                );
-            }
-            _ => {}
+        if let Some(path) = to_pixel_path(&self.elements, &ctx.scene_config) {
+            self.draw_config.render_path(ctx, &path);
         }
     }
 }
 
-impl Mobject for SVGPath {}
+impl Mobject for SVGPath {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(control_points(&self.elements))
+    }
+
+    fn control_points(&self) -> Vec<nalgebra::Point3<GMFloat>> {
+        control_points(&self.elements)
+    }
+
+    fn set_control_points(&mut self, points: &[nalgebra::Point3<GMFloat>]) {
+        set_control_points(&mut self.elements, points);
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        self.elements.clone()
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
 
 pub fn open_svg_file(svg_filepath: &str) -> MobjectGroup {
     let mut svg_file = fs::File::options()
@@ -184,7 +149,7 @@ pub fn open_svg_file(svg_filepath: &str) -> MobjectGroup {
     let mut svg_str_buf = String::new();
     svg_file.read_to_string(&mut svg_str_buf);
     let tree = usvg::Tree::from_str(&svg_str_buf, &Default::default()).unwrap();
-    let mut paths: Vec<SVGPath> = vec![];
+    let mut mobjects: Vec<Box<dyn Mobject>> = vec![];
     for node in tree.root.descendants() {
         let n = &*node.borrow();
         match n {
@@ -192,19 +157,38 @@ pub fn open_svg_file(svg_filepath: &str) -> MobjectGroup {
                 //we don't care for now
             }
             NodeKind::Image(img) => {
-                //we don't care for now
+                if let Some(image) = svg_image(img, node.abs_transform()) {
+                    mobjects.push(Box::new(image));
+                }
             }
             NodeKind::Path(path) => {
                 //apply transform
                 let mut svg_path = SVGPath::new();
+                if !path.id.is_empty() {
+                    svg_path.name = Some(path.id.clone());
+                }
                 let transform = node.abs_transform();
+                // svg space to scene space before the final scaling, y points up
+                let to_scene = nalgebra::Transform3::from_matrix_unchecked(
+                    nalgebra::Matrix4::new_nonuniform_scaling(&nalgebra::Vector3::new(
+                        1.0, -1.0, 1.0,
+                    )),
+                ) * affine(
+                    transform.sx,
+                    transform.ky,
+                    transform.kx,
+                    transform.sy,
+                    transform.tx,
+                    transform.ty,
+                );
+                svg_path.draw_config = svg_draw_config(path, to_scene);
                 let path_data = &path.data;
                 for e in path_data.segments() {
                     let pe = process_path_element(e, transform);
                     svg_path.elements.push(pe);
                 }
                 svg_path.flip_y_coordinate();
-                paths.push(svg_path);
+                mobjects.push(Box::new(svg_path));
             }
             NodeKind::Text(text) => {
                 //we don't care for now
@@ -212,19 +196,178 @@ pub fn open_svg_file(svg_filepath: &str) -> MobjectGroup {
         }
     }
 
-    let mut grp_mobj = MobjectGroup {
-        mobjects: paths
-            .into_iter()
-            .map(|p| Box::new(p) as Box<dyn Mobject>)
-            .collect(),
-    };
+    let mut grp_mobj = MobjectGroup::new(mobjects);
 
-    let scaling_matrix = nalgebra::Matrix4::new_scaling(0.1);
+    let scaling_matrix = nalgebra::Matrix4::new_scaling(SVG_UNIT);
     grp_mobj.transform(nalgebra::Transform::from_matrix_unchecked(scaling_matrix));
 
     grp_mobj
 }
 
+/// png or jpeg `image` element fitted into its box, centered and keeping its aspect
+/// ratio; other alignments of `preserveAspectRatio`, gifs and nested svgs are skipped
+fn svg_image(img: &usvg::Image, transform: usvg::Transform) -> Option<ImageMobject> {
+    let data = match &img.kind {
+        usvg::ImageKind::PNG(data) | usvg::ImageKind::JPEG(data) => data,
+        _ => {
+            info!("skipping image {}, only png and jpeg are supported", img.id);
+            return None;
+        }
+    };
+    let pixmap = match decode_image(data) {
+        Ok(pixmap) => pixmap,
+        Err(e) => {
+            error!("skipping image {}: {}", img.id, e);
+            return None;
+        }
+    };
+    let rect = img.view_box.rect;
+    let scale = (rect.width() / pixmap.width() as f32).min(rect.height() / pixmap.height() as f32);
+    let (w, h) = (
+        pixmap.width() as f32 * scale,
+        pixmap.height() as f32 * scale,
+    );
+    let (x, y) = (
+        rect.x() + (rect.width() - w) / 2.0,
+        rect.y() + (rect.height() - h) / 2.0,
+    );
+    // svg space is y down, flip like the paths
+    let corner = |cx: f32, cy: f32| {
+        let mut p = usvg::tiny_skia_path::Point::from_xy(cx, cy);
+        transform.map_point(&mut p);
+        nalgebra::Point3::new(p.x as GMFloat, -p.y as GMFloat, 0.0)
+    };
+    let mut image = ImageMobject::new(pixmap);
+    image.upper_left = corner(x, y);
+    image.lower_left = corner(x, y + h);
+    image.lower_right = corner(x + w, y + h);
+    if matches!(img.rendering_mode, usvg::ImageRendering::OptimizeSpeed) {
+        image.filter_quality = tiny_skia::FilterQuality::Nearest;
+    }
+    Some(image)
+}
+
+/// size of one svg user unit in scene units
+const SVG_UNIT: GMFloat = 0.1;
+
+/// svg style affine transform as a 3d transform in the xy plane
+fn affine(
+    sx: GMFloat,
+    ky: GMFloat,
+    kx: GMFloat,
+    sy: GMFloat,
+    tx: GMFloat,
+    ty: GMFloat,
+) -> nalgebra::Transform3<GMFloat> {
+    nalgebra::Transform3::from_matrix_unchecked(nalgebra::Matrix4::new(
+        sx, kx, 0.0, tx, //
+        ky, sy, 0.0, ty, //
+        0.0, 0.0, 1.0, 0.0, //
+        0.0, 0.0, 0.0, 1.0,
+    ))
+}
+
+/// fill and stroke of an svg path, `to_scene` maps the path's own coordinates to
+/// the scene; patterns fall back to the default color
+fn svg_draw_config(path: &usvg::Path, to_scene: nalgebra::Transform3<GMFloat>) -> DrawConfig {
+    let color = |paint: &usvg::Paint| match paint {
+        usvg::Paint::Color(c) => Color::new(c.red, c.green, c.blue, 0xff),
+        _ => Color::default(),
+    };
+    let gradient = |paint: &usvg::Paint| svg_gradient(paint, path, to_scene);
+    let mut draw_config = DrawConfig {
+        stroke_width: 0.0,
+        ..Default::default()
+    };
+    if let Some(fill) = &path.fill {
+        draw_config.fill_color = color(&fill.paint);
+        draw_config.fill_gradient = gradient(&fill.paint);
+        draw_config.fill_opacity = fill.opacity.get();
+        draw_config.fill_rule = match fill.rule {
+            usvg::FillRule::NonZero => FillRule::Winding,
+            usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+    }
+    if let Some(stroke) = &path.stroke {
+        // strokes don't scale with the mobject, so bake in the svg transform once
+        let m = to_scene.matrix();
+        let stroke_scale = (m[(0, 0)] * m[(1, 1)] - m[(0, 1)] * m[(1, 0)]).abs().sqrt();
+        draw_config.stroke_width = stroke.width.get() * stroke_scale * SVG_UNIT;
+        draw_config.stroke_color = color(&stroke.paint);
+        draw_config.stroke_gradient = gradient(&stroke.paint);
+        draw_config.stroke_opacity = stroke.opacity.get();
+        draw_config.miter_limit = stroke.miterlimit.get();
+        draw_config.line_cap = match stroke.linecap {
+            usvg::LineCap::Butt => LineCap::Butt,
+            usvg::LineCap::Round => LineCap::Round,
+            usvg::LineCap::Square => LineCap::Square,
+        };
+        draw_config.line_join = match stroke.linejoin {
+            usvg::LineJoin::Miter => LineJoin::Miter,
+            usvg::LineJoin::MiterClip => LineJoin::MiterClip,
+            usvg::LineJoin::Round => LineJoin::Round,
+            usvg::LineJoin::Bevel => LineJoin::Bevel,
+        };
+    }
+    draw_config
+}
+
+/// the gradient of a paint in scene space, None for plain colors and patterns
+fn svg_gradient(
+    paint: &usvg::Paint,
+    path: &usvg::Path,
+    to_scene: nalgebra::Transform3<GMFloat>,
+) -> Option<Gradient> {
+    let point = |x: GMFloat, y: GMFloat| nalgebra::Point3::new(x, y, 0.0);
+    let (kind, base) = match paint {
+        usvg::Paint::LinearGradient(g) => (
+            GradientKind::Linear {
+                start: point(g.x1, g.y1),
+                end: point(g.x2, g.y2),
+            },
+            &g.base,
+        ),
+        usvg::Paint::RadialGradient(g) => (
+            GradientKind::Radial {
+                center: point(g.cx, g.cy),
+                focal: point(g.fx, g.fy),
+                radius: g.r.get(),
+            },
+            &g.base,
+        ),
+        _ => return None,
+    };
+    let stops = base
+        .stops
+        .iter()
+        .map(|s| {
+            let alpha = (s.opacity.get() * 255.0).round() as u8;
+            GradientStop::new(
+                s.offset.get(),
+                Color::new(s.color.red, s.color.green, s.color.blue, alpha),
+            )
+        })
+        .collect();
+    let mut transform = to_scene;
+    if base.units == usvg::Units::ObjectBoundingBox {
+        // gradient coordinates are fractions of the path's bounding box
+        let b = path.data.bounds();
+        transform *= affine(b.width(), 0.0, 0.0, b.height(), b.x(), b.y());
+    }
+    let t = &base.transform;
+    transform *= affine(t.sx, t.ky, t.kx, t.sy, t.tx, t.ty);
+    Some(Gradient {
+        kind,
+        stops,
+        spread: match base.spread_method {
+            usvg::SpreadMethod::Pad => SpreadMode::Pad,
+            usvg::SpreadMethod::Reflect => SpreadMode::Reflect,
+            usvg::SpreadMethod::Repeat => SpreadMode::Repeat,
+        },
+        transform,
+    })
+}
+
 pub fn process_path_element(e: PathSegment, transform: tiny_skia::Transform) -> PathElement {
     match e {
         PathSegment::MoveTo(p) => {
@@ -0,0 +1,465 @@
+use nalgebra::{Point3, Vector3};
+
+use crate::{theme::theme, Color, Context, GMFloat, GMPoint, Scene};
+
+use super::{
+    distribute_control_points, polygon::Polygon, text::Text, BoundingBox, Draw, DrawConfig,
+    Mobject, PolyLine, SimpleLine, Transform,
+};
+
+pub const DEFAULT_CELL_FONT_SIZE: GMFloat = 6.0;
+pub const DEFAULT_CELL_BUFFER: (GMFloat, GMFloat) = (0.6, 0.3);
+
+// grid lines sit a bit lighter than the theme's thin stroke so they don't crowd the cells
+fn line_draw_config() -> DrawConfig {
+    let theme = theme();
+    DrawConfig::stroked(theme.foreground, theme.thin_stroke_width * 0.75)
+}
+
+/// text cells laid out in rows and columns, centered at origin
+#[derive(Clone)]
+pub struct Table {
+    cells: Vec<Vec<Text>>,
+    /// corners of each cell box, counter-clockwise from the lower left
+    cell_boxes: Vec<Vec<[GMPoint; 4]>>,
+    grid_lines: Vec<SimpleLine>,
+    highlights: Vec<Polygon>,
+}
+
+impl Table {
+    /// `buff` is the horizontal and vertical space between the contents of neighbour cells
+    pub fn new(entries: Vec<Vec<String>>, buff: (GMFloat, GMFloat), include_grid: bool) -> Self {
+        let column_count = entries.iter().map(|r| r.len()).max().unwrap_or(0);
+        let cells: Vec<Vec<Text>> = entries
+            .into_iter()
+            .map(|row| {
+                let mut row: Vec<Text> = row
+                    .into_iter()
+                    .map(|e| {
+                        Text::new(
+                            e,
+                            Point3::origin(),
+                            DEFAULT_CELL_FONT_SIZE,
+                            DrawConfig::text_default(),
+                        )
+                    })
+                    .collect();
+                // ragged rows get empty cells
+                row.resize_with(column_count, || {
+                    Text::new(
+                        String::new(),
+                        Point3::origin(),
+                        DEFAULT_CELL_FONT_SIZE,
+                        DrawConfig::text_default(),
+                    )
+                });
+                row
+            })
+            .collect();
+
+        let width = |t: &Text| t.bounding_box().map(|b| b.width()).unwrap_or(0.0);
+        let height = |t: &Text| t.bounding_box().map(|b| b.height()).unwrap_or(0.0);
+        let column_widths: Vec<GMFloat> = (0..column_count)
+            .map(|j| cells.iter().map(|r| width(&r[j])).fold(0.0, GMFloat::max) + buff.0)
+            .collect();
+        let row_heights: Vec<GMFloat> = cells
+            .iter()
+            .map(|r| r.iter().map(height).fold(0.0, GMFloat::max) + buff.1)
+            .collect();
+        let total_width: GMFloat = column_widths.iter().sum();
+        let total_height: GMFloat = row_heights.iter().sum();
+
+        let left = -total_width / 2.0;
+        let top = total_height / 2.0;
+        let mut column_edges = vec![left];
+        for w in &column_widths {
+            column_edges.push(column_edges.last().unwrap() + w);
+        }
+        let mut row_edges = vec![top];
+        for h in &row_heights {
+            row_edges.push(row_edges.last().unwrap() - h);
+        }
+
+        let mut table = Self {
+            cells,
+            cell_boxes: vec![],
+            grid_lines: vec![],
+            highlights: vec![],
+        };
+        for (i, row) in table.cells.iter_mut().enumerate() {
+            let mut boxes = vec![];
+            for (j, cell) in row.iter_mut().enumerate() {
+                let (x0, x1) = (column_edges[j], column_edges[j + 1]);
+                let (y0, y1) = (row_edges[i + 1], row_edges[i]);
+                if let Some(b) = cell.bounding_box() {
+                    let center = b.center();
+                    cell.move_this(Vector3::new(
+                        (x0 + x1) / 2.0 - center.x,
+                        (y0 + y1) / 2.0 - center.y,
+                        0.0,
+                    ));
+                }
+                boxes.push([
+                    Point3::new(x0, y0, 0.0),
+                    Point3::new(x1, y0, 0.0),
+                    Point3::new(x1, y1, 0.0),
+                    Point3::new(x0, y1, 0.0),
+                ]);
+            }
+            table.cell_boxes.push(boxes);
+        }
+        if include_grid {
+            let bottom = *row_edges.last().unwrap();
+            let right = *column_edges.last().unwrap();
+            for y in &row_edges {
+                table.grid_lines.push(SimpleLine {
+                    p0: Point3::new(left, *y, 0.0),
+                    p1: Point3::new(right, *y, 0.0),
+                    draw_config: line_draw_config(),
+                });
+            }
+            for x in &column_edges {
+                table.grid_lines.push(SimpleLine {
+                    p0: Point3::new(*x, top, 0.0),
+                    p1: Point3::new(*x, bottom, 0.0),
+                    draw_config: line_draw_config(),
+                });
+            }
+        }
+        table
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.cells.first().map(|r| r.len()).unwrap_or(0)
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> &Text {
+        &self.cells[row][column]
+    }
+
+    pub fn cell_mut(&mut self, row: usize, column: usize) -> &mut Text {
+        &mut self.cells[row][column]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> Vec<&mut Text> {
+        self.cells[row].iter_mut().collect()
+    }
+
+    pub fn column_mut(&mut self, column: usize) -> Vec<&mut Text> {
+        self.cells.iter_mut().map(|r| &mut r[column]).collect()
+    }
+
+    /// corners of the box around a cell, counter-clockwise from the lower left
+    pub fn cell_box(&self, row: usize, column: usize) -> [GMPoint; 4] {
+        self.cell_boxes[row][column]
+    }
+
+    pub fn set_cell_color(&mut self, row: usize, column: usize, color: Color) {
+        self.cells[row][column].draw_config.fill_color = color;
+    }
+
+    pub fn set_row_color(&mut self, row: usize, color: Color) {
+        self.row_mut(row)
+            .into_iter()
+            .for_each(|c| c.draw_config.fill_color = color);
+    }
+
+    pub fn set_column_color(&mut self, column: usize, color: Color) {
+        self.column_mut(column)
+            .into_iter()
+            .for_each(|c| c.draw_config.fill_color = color);
+    }
+
+    /// fill the box behind a cell, cells outside the table are ignored
+    pub fn highlight_cell(&mut self, row: usize, column: usize, color: Color) {
+        let Some(cell_box) = self.cell_boxes.get(row).and_then(|r| r.get(column)) else {
+            return;
+        };
+        self.push_highlight(cell_box.to_vec(), color);
+    }
+
+    pub fn highlight_row(&mut self, row: usize, color: Color) {
+        let Some(boxes) = self.cell_boxes.get(row) else {
+            return;
+        };
+        let (Some(first), Some(last)) = (boxes.first(), boxes.last()) else {
+            return;
+        };
+        self.push_highlight(vec![first[0], last[1], last[2], first[3]], color);
+    }
+
+    pub fn highlight_column(&mut self, column: usize, color: Color) {
+        let first = self.cell_boxes.first().and_then(|r| r.get(column));
+        let last = self.cell_boxes.last().and_then(|r| r.get(column));
+        let (Some(first), Some(last)) = (first, last) else {
+            return;
+        };
+        self.push_highlight(vec![last[0], last[1], first[2], first[3]], color);
+    }
+
+    fn push_highlight(&mut self, corners: Vec<GMPoint>, color: Color) {
+        let mut highlight = Polygon::new(corners);
+        highlight.draw_config.fill_color = color;
+        self.highlights.push(highlight);
+    }
+
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// corners of the whole table, counter-clockwise from the lower left, None when it
+    /// has no cells
+    fn outer_box(&self) -> Option<[GMPoint; 4]> {
+        let first_row = self.cell_boxes.first()?;
+        let last_row = self.cell_boxes.last()?;
+        Some([
+            last_row.first()?[0],
+            last_row.last()?[1],
+            first_row.last()?[2],
+            first_row.first()?[3],
+        ])
+    }
+}
+
+impl Transform for Table {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        for row in &mut self.cells {
+            row.iter_mut().for_each(|c| c.transform(transform));
+        }
+        for row in &mut self.cell_boxes {
+            for b in row.iter_mut() {
+                b.iter_mut().for_each(|p| *p = transform * (*p));
+            }
+        }
+        self.grid_lines
+            .iter_mut()
+            .for_each(|l| l.transform(transform));
+        self.highlights
+            .iter_mut()
+            .for_each(|h| h.transform(transform));
+    }
+}
+
+impl Draw for Table {
+    fn draw(&self, ctx: &mut Context) {
+        self.highlights.iter().for_each(|h| h.draw(ctx));
+        self.grid_lines.iter().for_each(|l| l.draw(ctx));
+        for row in &self.cells {
+            row.iter().for_each(|c| c.draw(ctx));
+        }
+    }
+}
+
+impl Mobject for Table {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(
+            self.cell_boxes
+                .iter()
+                .flatten()
+                .map(|b| BoundingBox::from_points(b.iter().copied()))
+                .chain(self.cells.iter().flatten().map(|c| c.bounding_box())),
+        )
+    }
+
+    /// the cell texts, row by row
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        self.cells
+            .iter()
+            .flatten()
+            .map(|c| c as &dyn Mobject)
+            .collect()
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        self.cells
+            .iter_mut()
+            .flatten()
+            .map(|c| c as &mut dyn Mobject)
+            .collect()
+    }
+
+    /// the cells, grid lines, highlights and cell boxes, so the whole table interpolates
+    fn control_points(&self) -> Vec<GMPoint> {
+        self.submobjects()
+            .into_iter()
+            .chain(self.grid_lines.iter().map(|l| l as &dyn Mobject))
+            .chain(self.highlights.iter().map(|h| h as &dyn Mobject))
+            .flat_map(|m| m.control_points())
+            .chain(self.cell_boxes.iter().flatten().flatten().copied())
+            .collect()
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        let mut parts: Vec<&mut dyn Mobject> = vec![];
+        for row in &mut self.cells {
+            parts.extend(row.iter_mut().map(|c| c as &mut dyn Mobject));
+        }
+        parts.extend(self.grid_lines.iter_mut().map(|l| l as &mut dyn Mobject));
+        parts.extend(self.highlights.iter_mut().map(|h| h as &mut dyn Mobject));
+        let rest = distribute_control_points(parts, points);
+        for (p, q) in self.cell_boxes.iter_mut().flatten().flatten().zip(rest) {
+            *p = *q;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatrixBracket {
+    Square,
+    /// vertical bars of a determinant
+    Bar,
+    None,
+}
+
+/// a bracketed table of math entries
+#[derive(Clone)]
+pub struct Matrix {
+    pub table: Table,
+    brackets: Vec<PolyLine>,
+}
+
+impl Matrix {
+    pub fn new(entries: Vec<Vec<String>>, bracket: MatrixBracket) -> Self {
+        let table = Table::new(entries, DEFAULT_CELL_BUFFER, false);
+        let mut brackets = vec![];
+        if let Some([lower_left, lower_right, upper_right, upper_left]) = table.outer_box() {
+            let tip = Vector3::new(DEFAULT_CELL_BUFFER.0 / 3.0, 0.0, 0.0);
+            // brackets are a little heavier than the thin stroke
+            let theme = theme();
+            let draw_config = DrawConfig::stroked(theme.foreground, theme.thin_stroke_width * 1.25);
+            let (left, right) = match bracket {
+                MatrixBracket::Square => (
+                    vec![upper_left + tip, upper_left, lower_left, lower_left + tip],
+                    vec![
+                        upper_right - tip,
+                        upper_right,
+                        lower_right,
+                        lower_right - tip,
+                    ],
+                ),
+                MatrixBracket::Bar => {
+                    (vec![upper_left, lower_left], vec![upper_right, lower_right])
+                }
+                MatrixBracket::None => (vec![], vec![]),
+            };
+            for points in [left, right] {
+                if !points.is_empty() {
+                    brackets.push(PolyLine {
+                        points,
+                        draw_config: draw_config.clone(),
+                    });
+                }
+            }
+        }
+        Self { table, brackets }
+    }
+
+    pub fn from_values(values: Vec<Vec<GMFloat>>, bracket: MatrixBracket) -> Self {
+        Self::new(
+            values
+                .into_iter()
+                .map(|r| r.into_iter().map(|v| format!("{}", v)).collect())
+                .collect(),
+            bracket,
+        )
+    }
+
+    /// determinant notation, the entries between vertical bars
+    pub fn determinant(entries: Vec<Vec<String>>) -> Self {
+        Self::new(entries, MatrixBracket::Bar)
+    }
+}
+
+impl Transform for Matrix {
+    fn transform(&mut self, transform: nalgebra::Transform3<GMFloat>) {
+        self.table.transform(transform);
+        self.brackets
+            .iter_mut()
+            .for_each(|b| b.transform(transform));
+    }
+}
+
+impl Draw for Matrix {
+    fn draw(&self, ctx: &mut Context) {
+        self.table.draw(ctx);
+        self.brackets.iter().for_each(|b| b.draw(ctx));
+    }
+}
+
+impl Mobject for Matrix {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(
+            self.brackets
+                .iter()
+                .map(|b| b.bounding_box())
+                .chain([self.table.bounding_box()]),
+        )
+    }
+
+    /// the entries, row by row
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        self.table.submobjects()
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        self.table.submobjects_mut()
+    }
+
+    fn control_points(&self) -> Vec<GMPoint> {
+        let mut points = self.table.control_points();
+        points.extend(self.brackets.iter().flat_map(|b| b.control_points()));
+        points
+    }
+
+    fn set_control_points(&mut self, points: &[GMPoint]) {
+        let count = self.table.control_points().len().min(points.len());
+        self.table.set_control_points(&points[..count]);
+        let brackets = self.brackets.iter_mut().map(|b| b as &mut dyn Mobject);
+        distribute_control_points(brackets.collect(), &points[count..]);
+    }
+}
+
+#[test]
+fn test_table_and_matrix() {
+    let mut ctx = Context::default();
+    let mut scene = Scene::default();
+    let entries = |rows: &[&[&str]]| -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|r| r.iter().map(|e| e.to_string()).collect())
+            .collect()
+    };
+    let mut table = Table::new(
+        entries(&[&["x", "f(x)"], &["1", "1"], &["2", "4"], &["3"]]),
+        DEFAULT_CELL_BUFFER,
+        true,
+    );
+    assert_eq!(table.column_count(), 2);
+    table.highlight_row(0, Color::new(0x33, 0x33, 0x66, 0xff));
+    table.highlight_cell(2, 1, Color::new(0x66, 0x33, 0x33, 0xff));
+    table.set_column_color(1, Color::new(0xff, 0xff, 0x00, 0xff));
+    table.move_this(Vector3::new(-3.0, 0.0, 0.0));
+    let mut matrix =
+        Matrix::from_values(vec![vec![1.0, 0.0], vec![0.0, 1.0]], MatrixBracket::Square);
+    matrix.move_this(Vector3::new(2.0, 1.5, 0.0));
+    let mut determinant = Matrix::determinant(entries(&[&["a", "b"], &["c", "d"]]));
+    determinant.move_this(Vector3::new(2.0, -1.5, 0.0));
+    scene.add(Box::new(table));
+    scene.add(Box::new(matrix));
+    scene.add(Box::new(determinant));
+    scene.save_png(&mut ctx, "table.png");
+
+    // empty tables have nothing to bracket or highlight
+    for empty in [vec![], vec![vec![]]] {
+        let matrix = Matrix::new(empty.clone(), MatrixBracket::Square);
+        assert!(matrix.bounding_box().is_none());
+        let mut table = Table::new(empty, DEFAULT_CELL_BUFFER, false);
+        table.highlight_row(0, Color::default());
+        table.highlight_column(0, Color::default());
+        table.highlight_cell(0, 0, Color::default());
+        assert!(table.highlights.is_empty());
+    }
+}
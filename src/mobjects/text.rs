@@ -1,21 +1,25 @@
 use std::fs;
 use std::io::Read;
+use std::ops::{Index, IndexMut};
+use std::slice::SliceIndex;
 
 use log::info;
 use rusttype::{point, Font, Scale};
 
 use crate::log_utils::setup_logger;
 use crate::mobjects::Draw;
-use crate::{log_utils, ContextType, GMFloat};
+use crate::{log_utils, Color, ContextType, GMFloat};
 use nalgebra::{Point2, Point3, Vector3};
 
-use super::path::PathElement;
-use super::{coordinate_change_x, coordinate_change_y, DrawConfig, Mobject, Transform};
+use super::path::{control_points, set_control_points, to_pixel_path, PathElement};
+use super::{distribute_control_points, BoundingBox, DrawConfig, Mobject, Transform};
 
+#[derive(Clone)]
 pub struct Text {
     pub text: String,
     glyph_paths: Vec<GlyphPath>,
-    pub position: Point3<GMFloat>,
+    /// where the text was laid out, follows transforms, see `set_position`
+    position: Point3<GMFloat>,
     pub font_size: GMFloat,
     pub draw_config: DrawConfig,
 }
@@ -54,10 +58,17 @@ impl Transform for Text {
         for g in &mut self.glyph_paths {
             g.transform(transform);
         }
+        self.position = transform * self.position;
+        self.draw_config.transform_gradients(transform);
     }
 }
 
-struct GlyphPath {
+/// outline of one visible character of a [`Text`], reachable through `text[i]`
+#[derive(Clone)]
+pub struct GlyphPath {
+    pub character: char,
+    /// overrides the draw config of the text for this glyph
+    pub draw_config: Option<DrawConfig>,
     glyph_position: Point2<GMFloat>,
     path_elements: Vec<PathElement>,
 }
@@ -67,19 +78,31 @@ impl Transform for GlyphPath {
         for p in &mut self.path_elements {
             p.transform(transform);
         }
+        if let Some(draw_config) = &mut self.draw_config {
+            draw_config.transform_gradients(transform);
+        }
     }
 }
 
 impl GlyphPath {
-    fn new(position: rusttype::Point<f32>) -> Self {
+    /// `origin` is where rusttype puts the outline origin, the glyph's pixel bounding box corner
+    fn new(character: char, origin: rusttype::Point<f32>, position: Point3<GMFloat>) -> Self {
         Self {
+            character,
+            draw_config: None,
             glyph_position: Point2::new(
-                position.x * SCALE_TEXT_FACTOR,
-                -position.y * SCALE_TEXT_FACTOR,
+                origin.x * SCALE_TEXT_FACTOR + position.x,
+                -origin.y * SCALE_TEXT_FACTOR + position.y,
             ),
             path_elements: vec![],
         }
     }
+
+    fn draw_with(&self, ctx: &mut crate::Context, draw_config: &DrawConfig) {
+        if let Some(path) = to_pixel_path(&self.path_elements, &ctx.scene_config) {
+            draw_config.render_path(ctx, &path);
+        }
+    }
 }
 
 pub const SCALE_TEXT_FACTOR: f32 = 0.1;
@@ -139,128 +162,55 @@ impl rusttype::OutlineBuilder for GlyphPath {
     }
 }
 
+impl Draw for GlyphPath {
+    fn draw(&self, ctx: &mut crate::Context) {
+        match &self.draw_config {
+            Some(draw_config) => self.draw_with(ctx, draw_config),
+            None => self.draw_with(ctx, &DrawConfig::filled(crate::theme::theme().foreground)),
+        }
+    }
+}
+
+impl Mobject for GlyphPath {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(control_points(&self.path_elements))
+    }
+
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        control_points(&self.path_elements)
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        set_control_points(&mut self.path_elements, points);
+    }
+
+    fn path_elements(&self) -> Vec<PathElement> {
+        self.path_elements.clone()
+    }
+
+    /// only set when the glyph is styled apart from its text
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        self.draw_config.as_ref()
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        self.draw_config.as_mut()
+    }
+}
+
 impl Draw for Text {
     fn draw(&self, ctx: &mut crate::Context) {
         if self.text.len() == 0 {
             return; //this is no text to draw
         }
-        let scale_factor = ctx.scene_config.scale_factor;
-        match &mut ctx.ctx_type {
-            ContextType::TinySKIA(pixmap) => {
-                for g in &self.glyph_paths {
-                    let mut pb = tiny_skia::PathBuilder::new();
-                    for path in &g.path_elements {
-                        match path {
-                            PathElement::MoveTo(p) => {
-                                let x = coordinate_change_x(
-                                    p.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y = coordinate_change_y(
-                                    p.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-                                pb.move_to(x, y);
-                            }
-                            PathElement::LineTo(p) => {
-                                let x = coordinate_change_x(
-                                    p.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y = coordinate_change_y(
-                                    p.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-                                pb.line_to(x, y);
-                            }
-                            PathElement::QuadTo(p1, p2) => {
-                                let x1 = coordinate_change_x(
-                                    p1.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y1 = coordinate_change_y(
-                                    p1.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let x2 = coordinate_change_x(
-                                    p2.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y2 = coordinate_change_y(
-                                    p2.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-                                pb.quad_to(x1, y1, x2, y2);
-                            }
-                            PathElement::CubicTo(p1, p2, p3) => {
-                                let x1 = coordinate_change_x(
-                                    p1.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y1 = coordinate_change_y(
-                                    p1.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let x2 = coordinate_change_x(
-                                    p2.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y2 = coordinate_change_y(
-                                    p2.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let x3 = coordinate_change_x(
-                                    p3.x + self.position.x,
-                                    ctx.scene_config.width,
-                                ) as f32
-                                    * scale_factor as f32;
-                                let y3 = coordinate_change_y(
-                                    p3.y + self.position.y,
-                                    ctx.scene_config.height,
-                                ) as f32
-                                    * scale_factor as f32;
-
-                                pb.cubic_to(x1, y1, x2, y2, x3, y3);
-                            }
-                            PathElement::Close => {
-                                pb.close();
-                            }
-                        }
-                    }
-                    let path = pb.finish().unwrap();
-                    let mut stroke = tiny_skia::Stroke::default();
-                    stroke.width = self.draw_config.stoke_width * scale_factor;
-                    stroke.line_cap = tiny_skia::LineCap::Round;
-                    let mut paint = tiny_skia::Paint::default();
-                    paint.set_color(self.draw_config.color.into());
-                    pixmap.fill_path(
-                        &path,
-                        &paint,
-                        Default::default(),
-                        tiny_skia::Transform::identity(),
-                        None,
-                    );
-                }
-            }
-            _ => {}
+        for g in &self.glyph_paths {
+            g.draw_with(ctx, g.draw_config.as_ref().unwrap_or(&self.draw_config));
         }
     }
 }
 
 impl Text {
-    fn new(
+    pub fn new(
         text: String,
         position: Point3<GMFloat>,
         font_size: GMFloat,
@@ -304,8 +254,14 @@ impl Text {
                 .unwrap();
             ((max_x - min_x) as usize, min_x)
         }; // great, rusttype help me to calculate advance width and Kerning Pair
-        for glyph in glyphs {
-            let mut glyph_path = GlyphPath::new(glyph.position());
+
+        // layout yields one glyph per char, whitespace has no outline and is skipped
+        for (character, glyph) in text.chars().zip(glyphs) {
+            let Some(bb) = glyph.pixel_bounding_box() else {
+                continue;
+            };
+            let mut glyph_path =
+                GlyphPath::new(character, point(bb.min.x as f32, bb.min.y as f32), position);
             glyph.build_outline(&mut glyph_path);
             glyph_paths.push(glyph_path);
         }
@@ -319,7 +275,82 @@ impl Text {
     }
 }
 
-impl Mobject for Text {}
+impl Text {
+    pub fn position(&self) -> Point3<GMFloat> {
+        self.position
+    }
+
+    /// move the text so that its layout origin lands on `position`
+    pub fn set_position(&mut self, position: Point3<GMFloat>) {
+        self.move_this(position - self.position);
+    }
+
+    pub fn glyphs(&self) -> &[GlyphPath] {
+        &self.glyph_paths
+    }
+
+    pub fn glyphs_mut(&mut self) -> &mut [GlyphPath] {
+        &mut self.glyph_paths
+    }
+}
+
+impl<I: SliceIndex<[GlyphPath]>> Index<I> for Text {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.glyph_paths[index]
+    }
+}
+
+impl<I: SliceIndex<[GlyphPath]>> IndexMut<I> for Text {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.glyph_paths[index]
+    }
+}
+
+impl Mobject for Text {
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::union_all(self.glyph_paths.iter().map(|g| g.bounding_box()))
+    }
+
+    fn submobjects(&self) -> Vec<&dyn Mobject> {
+        self.glyph_paths.iter().map(|g| g as &dyn Mobject).collect()
+    }
+
+    fn submobjects_mut(&mut self) -> Vec<&mut dyn Mobject> {
+        self.glyph_paths
+            .iter_mut()
+            .map(|g| g as &mut dyn Mobject)
+            .collect()
+    }
+
+    /// the glyph points followed by the position, so it moves along with them
+    fn control_points(&self) -> Vec<Point3<GMFloat>> {
+        let mut points: Vec<_> = self
+            .glyph_paths
+            .iter()
+            .flat_map(|g| g.control_points())
+            .collect();
+        points.push(self.position);
+        points
+    }
+
+    fn set_control_points(&mut self, points: &[Point3<GMFloat>]) {
+        let Some((position, rest)) = points.split_last() else {
+            return;
+        };
+        distribute_control_points(self.submobjects_mut(), rest);
+        self.position = *position;
+    }
+
+    fn draw_config(&self) -> Option<&DrawConfig> {
+        Some(&self.draw_config)
+    }
+
+    fn draw_config_mut(&mut self) -> Option<&mut DrawConfig> {
+        Some(&mut self.draw_config)
+    }
+}
 
 #[test]
 fn test_draw_text() {
@@ -330,7 +361,7 @@ fn test_draw_text() {
         "你好呀".to_owned(),
         Point3::new(0.0, 1.0, 0.0),
         32.0,
-        DrawConfig::default(),
+        DrawConfig::text_default(),
     );
     let rotation = nalgebra::Matrix4::new_rotation_wrt_point(
         nalgebra::Vector3::new(0.0, 0.0, 1.0),
@@ -345,3 +376,25 @@ fn test_draw_text() {
         _ => {}
     }
 }
+
+#[test]
+fn test_text_position() {
+    let mut text = Text::new(
+        "ab".to_owned(),
+        Point3::new(-1.0, 1.0, 0.0),
+        16.0,
+        DrawConfig::filled(Color::default()),
+    );
+    let before = text.bounding_box().unwrap();
+    text.set_position(Point3::new(2.0, -1.0, 0.0));
+    assert_eq!(text.position(), Point3::new(2.0, -1.0, 0.0));
+    let after = text.bounding_box().unwrap();
+    assert!((after.min - before.min - Vector3::new(3.0, -2.0, 0.0)).norm() < 1e-5);
+
+    // the position is interpolated along with the glyphs
+    let mut points = text.control_points();
+    points.iter_mut().for_each(|p| p.x += 1.0);
+    text.set_control_points(&points);
+    assert_eq!(text.position(), Point3::new(3.0, -1.0, 0.0));
+    assert!((text.bounding_box().unwrap().min.x - after.min.x - 1.0).abs() < 1e-5);
+}
@@ -157,7 +157,7 @@ impl Draw for ThreeDViewport {
                     new_pixmap.as_ref(),
                     &PixmapPaint::default(),
                     tiny_skia::Transform::identity(),
-                    None,
+                    ctx.mask.as_ref(),
                 );
             }
             _ => {}
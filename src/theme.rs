@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::color::{self, Color};
+use crate::GMFloat;
+
+/// default look of everything drawn, switch it with [`set_theme`] or [`with_theme`]
+/// before building the mobjects of a video
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub background: Color,
+    /// text, labels, axes and table rules
+    pub foreground: Color,
+    /// stroke and fill of shapes that don't pick their own color
+    pub primary: Color,
+    pub stroke_width: GMFloat,
+    /// axes, rules, braces and other guide lines
+    pub thin_stroke_width: GMFloat,
+    /// colors handed out in turn, e.g. to chart series
+    pub palette: Vec<Color>,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            background: color::BLACK,
+            foreground: color::WHITE,
+            primary: Color::default(),
+            stroke_width: 0.25,
+            thin_stroke_width: 0.04,
+            palette: vec![
+                color::BLUE,
+                color::GREEN,
+                color::YELLOW,
+                color::RED,
+                color::PURPLE,
+                color::ORANGE,
+            ],
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            background: color::WHITE,
+            foreground: color::BLACK,
+            primary: color::BLUE_E,
+            stroke_width: 0.25,
+            thin_stroke_width: 0.04,
+            palette: vec![
+                color::BLUE_E,
+                color::GREEN_E,
+                color::GOLD_E,
+                color::RED_E,
+                color::PURPLE_E,
+                color::DARK_BROWN,
+            ],
+        }
+    }
+
+    /// `index`-th palette color, wrapping around
+    pub fn palette_color(&self, index: usize) -> Color {
+        match self.palette.len() {
+            0 => self.primary,
+            n => self.palette[index % n],
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+thread_local! {
+    // per thread, so tests running side by side don't restyle each other
+    static THEME: RefCell<Rc<Theme>> = RefCell::new(Rc::new(Theme::dark()));
+}
+
+/// the theme defaults are read from on this thread, dark until [`set_theme`] is called;
+/// shared rather than copied, so reading a field is cheap
+pub fn theme() -> Rc<Theme> {
+    THEME.with(|t| t.borrow().clone())
+}
+
+/// restyles every mobject built afterwards on this thread and the background of every
+/// render
+pub fn set_theme(theme: Theme) {
+    THEME.with(|t| *t.borrow_mut() = Rc::new(theme));
+}
+
+/// run `f` with `theme` set, the previous theme is back afterwards
+pub fn with_theme<R>(theme: Theme, f: impl FnOnce() -> R) -> R {
+    let previous = THEME.with(|t| t.replace(Rc::new(theme)));
+    let result = f();
+    THEME.with(|t| *t.borrow_mut() = previous);
+    result
+}
+
+#[test]
+fn test_theme() {
+    use crate::mobjects::{DrawConfig, Rectangle, Transform};
+    use crate::{Context, ContextType, Scene};
+
+    let dark = theme();
+    assert_eq!(*dark, Theme::dark());
+    assert_eq!(dark.palette_color(7), color::GREEN);
+
+    let custom = Theme {
+        primary: color::TEAL,
+        stroke_width: 0.1,
+        ..Theme::light()
+    };
+    let mut ctx = Context::default();
+    with_theme(custom, || {
+        let style = DrawConfig::default();
+        assert_eq!(style.stroke_color, color::TEAL);
+        assert_eq!(style.stroke_width, 0.1);
+
+        let mut scene = Scene::default();
+        let mut square = Rectangle::default();
+        square.scale(3.0);
+        square.move_this(nalgebra::Vector3::new(-1.5, -1.5, 0.0));
+        scene.add(Box::new(square));
+        scene.save_png(&mut ctx, "theme.png");
+    });
+    let ContextType::TinySKIA(pixmap) = &ctx.ctx_type else {
+        unreachable!()
+    };
+    let corner = pixmap.pixel(0, 0).unwrap();
+    assert_eq!(
+        (corner.red(), corner.green(), corner.blue()),
+        (0xff, 0xff, 0xff)
+    );
+    assert_eq!(*theme(), Theme::dark());
+}
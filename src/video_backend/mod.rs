@@ -4,6 +4,8 @@ use std::sync::mpsc::{self, channel, Receiver, Sender};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread::{self, JoinHandle};
 
+use log::warn;
+
 const BLOCK_SIZE: usize = 240;
 pub enum VideoBackendType {
     FFMPEG(FFMPEGBackend),
@@ -40,6 +42,8 @@ pub struct VideoConfig {
     pub output_width: u32,
     pub output_height: u32,
     pub color_order: ColorOrder,
+    /// keep the alpha channel, needs an encoder that can store it
+    pub alpha: bool,
 }
 
 pub struct FFMPEGBackend {
@@ -53,6 +57,10 @@ pub enum FFMPEGEncoder {
     libx265,
     hevc_nvenc,
     hevc_vaapi,
+    /// prores 4444 in a .mov, keeps alpha
+    prores_ks,
+    /// vp9 in a .webm, keeps alpha
+    libvpx_vp9,
 }
 
 impl FFMPEGEncoder {
@@ -62,8 +70,13 @@ impl FFMPEGEncoder {
             Self::libx265 => "libx265",
             Self::hevc_nvenc => "hevc_nvenc",
             Self::hevc_vaapi => "hevc_vaapi",
+            Self::prores_ks => "prores_ks",
+            Self::libvpx_vp9 => "libvpx-vp9",
         }
     }
+    fn supports_alpha(&self) -> bool {
+        matches!(self, Self::prores_ks | Self::libvpx_vp9)
+    }
 }
 pub struct FFMPEGConfig {
     pub ffmpeg_encoder: FFMPEGEncoder,
@@ -144,6 +157,7 @@ impl VideoBackend {
 struct FFMPEGOutputOptionBuilder {
     high_quality: bool,
     encoder: FFMPEGEncoder,
+    alpha: bool,
 }
 
 impl FFMPEGOutputOptionBuilder {
@@ -187,6 +201,20 @@ impl FFMPEGOutputOptionBuilder {
                     vec!["-preset", "p1"]
                 }
             }
+            FFMPEGEncoder::prores_ks => {
+                if self.high_quality {
+                    vec!["-profile:v", "4444xq"]
+                } else {
+                    vec!["-profile:v", "4444"]
+                }
+            }
+            FFMPEGEncoder::libvpx_vp9 => {
+                if self.high_quality {
+                    vec!["-deadline", "best"]
+                } else {
+                    vec!["-deadline", "realtime"]
+                }
+            }
             _ => {
                 if self.high_quality {
                     vec!["-preset", "veryslow"]
@@ -195,8 +223,19 @@ impl FFMPEGOutputOptionBuilder {
                 }
             }
         };
-        //vaapi only support "vaapi" pix_fmt
-        if !matches!(self.encoder, FFMPEGEncoder::hevc_vaapi) {
+        if self.alpha && !self.encoder.supports_alpha() {
+            warn!(
+                "{} can't store alpha, the video will be opaque",
+                self.encoder.get_encoder_name()
+            );
+        }
+        //alpha needs a yuva pix_fmt, vaapi only support "vaapi" pix_fmt
+        if self.alpha && self.encoder.supports_alpha() {
+            match self.encoder {
+                FFMPEGEncoder::prores_ks => quality_options.extend(["-pix_fmt", "yuva444p10le"]),
+                _ => quality_options.extend(["-pix_fmt", "yuva420p"]),
+            }
+        } else if !matches!(self.encoder, FFMPEGEncoder::hevc_vaapi) {
             if self.high_quality {
                 quality_options.extend(["-pix_fmt", "yuv444p"]);
             } else {
@@ -234,6 +273,7 @@ impl FFMPEGBackend {
         let encoder_option_builder = FFMPEGOutputOptionBuilder {
             high_quality: high_profile,
             encoder: encoder_config,
+            alpha: video_config.alpha,
         };
         
         encoder_option_builder.build_option(&mut args);